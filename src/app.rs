@@ -1,6 +1,9 @@
 //! Main application logic and TUI event loop.
 
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::io;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 /// Width of the sidebar panel in characters
@@ -9,24 +12,47 @@ const SIDEBAR_WIDTH: u16 = 25;
 /// Horizontal scroll step for config panel
 const HORIZONTAL_SCROLL_STEP: u16 = 4;
 
+/// Share of the content width taken by the system panel when shown
+const SYSTEM_PANEL_PERCENT: u16 = 35;
+
+/// Rows scrolled by PageUp/PageDown in the table view
+const TABLE_PAGE_ROWS: isize = 10;
+
+/// Share of the content height taken by the log panel when shown
+const LOG_PANEL_PERCENT: u16 = 30;
+
 use anyhow::{Context, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Position, Rect},
     Terminal,
 };
 
-use crate::cli::AppConfig;
-use crate::data::{ComparisonState, Config, Metric, Project, Run, Storage};
+use crate::cli::{AppConfig, DataSource, ResumeMode};
+use crate::data::{
+    ComparisonState, Config, DbWatcher, Histogram, LoadRequest, LoadResponse, Loader,
+    LogFileBackend, Media, Metric, MetricsRequest, MetricsUpdate, MlflowBackend, MultiRootBackend,
+    ParquetBackend, Project, RichValues, RichValuesUpdate, Run, Storage, StorageBackend, Table,
+    TensorBoardBackend,
+};
 use crate::ui::{
-    chart::MetricsChart,
+    chart::{ChartView, MetricsChart, RunAxis, XAxis, YScale},
+    histogram::HistogramView,
+    log::{log_lines, LogPanel},
+    media::{MediaPreview, MediaView},
     metric_selector::{MetricSelector, MetricSlotState},
-    widgets::{ConfigPanel, ConfigPanelState, ProjectList, RunList, StatusBar},
+    smoothing::Smoothing,
+    system::SystemPanel,
+    table::{TableView, TableViewState},
+    widgets::{list_index_at, ConfigPanel, ConfigPanelState, ProjectList, RunList, StatusBar},
     HelpOverlay,
 };
 
@@ -36,40 +62,74 @@ pub enum FocusedPanel {
     Projects,
     Runs,
     Config,
+    Log,
+    /// The metrics chart, for zooming and panning; entered with `z`
+    Chart,
 }
 
 impl FocusedPanel {
-    fn next(self) -> Self {
+    /// Next panel; the log panel is skipped unless `with_log`, and the chart
+    /// is left to the cycle
+    fn next(self, with_log: bool) -> Self {
         match self {
             FocusedPanel::Projects => FocusedPanel::Runs,
             FocusedPanel::Runs => FocusedPanel::Config,
-            FocusedPanel::Config => FocusedPanel::Projects,
+            FocusedPanel::Config if with_log => FocusedPanel::Log,
+            FocusedPanel::Config | FocusedPanel::Log | FocusedPanel::Chart => {
+                FocusedPanel::Projects
+            }
         }
     }
 
-    fn prev(self) -> Self {
+    /// Previous panel; the log panel is skipped unless `with_log`
+    fn prev(self, with_log: bool) -> Self {
         match self {
-            FocusedPanel::Projects => FocusedPanel::Config,
+            FocusedPanel::Projects | FocusedPanel::Chart if with_log => FocusedPanel::Log,
+            FocusedPanel::Projects | FocusedPanel::Chart => FocusedPanel::Config,
             FocusedPanel::Runs => FocusedPanel::Projects,
             FocusedPanel::Config => FocusedPanel::Runs,
+            FocusedPanel::Log => FocusedPanel::Config,
         }
     }
 }
 
+/// Screen areas of the panels drawn by the last frame, for mouse input
+#[derive(Debug, Clone, Copy, Default)]
+struct PanelAreas {
+    projects: Rect,
+    runs: Rect,
+    config: Rect,
+    log: Rect,
+    chart: Rect,
+    metric_selector: Rect,
+}
+
 /// Application state
 pub struct App {
     // Configuration
     config: AppConfig,
 
     // Data
-    storage: Storage,
+    loader: Loader,
+    /// Project requested on the command line, selected once projects first load
+    initial_project: Option<String>,
     projects: Vec<Project>,
     runs: Vec<Run>,
     metrics: Vec<Metric>,
     metric_names: Vec<String>,
+    /// (project, run) that `metrics` was loaded for, used to refresh incrementally
+    loaded_run: Option<(String, String)>,
+    /// System metrics stored apart from training metrics, for `system_run`
+    system_metrics: Vec<Metric>,
+    system_run: Option<(String, String)>,
+    /// Histograms and other non-scalar values of `rich_run`, listed after the scalar metrics
+    rich_values: RichValues,
+    rich_run: Option<(String, String)>,
 
     // Comparison state
     comparison: ComparisonState,
+    /// Loaded runs of the project whose latest value of some metric is non-finite
+    diverged_runs: HashSet<String>,
 
     // UI State
     focused: FocusedPanel,
@@ -77,12 +137,32 @@ pub struct App {
     selected_run: usize,
     metric_slot: MetricSlotState,
     show_help: bool,
+    show_system: bool,
+    show_log: bool,
+    resume_mode: ResumeMode,
+    x_axis: XAxis,
+    /// Y-axis scale chosen for each metric name this session (linear if absent)
+    y_scales: HashMap<String, YScale>,
+    smoothing: Smoothing,
+    /// Zoom and pan of the metrics chart, kept across refreshes and runs
+    chart_view: ChartView,
+    /// Entry shown by the histogram, table and media views; `None` follows the latest step
+    step_cursor: Option<usize>,
+    table_view: TableViewState,
+    media_preview: MediaPreview,
 
     // Config panel state (consolidated)
     config_panel: ConfigPanelState,
     // Cached config lines for search (regenerated when run changes)
     cached_config_lines: Vec<String>,
 
+    // Log panel state, and its lines (regenerated when text values load)
+    log_panel: ConfigPanelState,
+    cached_log_lines: Vec<String>,
+
+    // Panel areas of the last frame (set while rendering)
+    areas: Cell<PanelAreas>,
+
     // Timing
     last_refresh: Instant,
 
@@ -94,132 +174,469 @@ pub struct App {
 }
 
 impl App {
-    /// Create a new App instance
+    /// Create a new App instance reading the data source selected in `config`
     pub fn new(config: AppConfig) -> Result<Self> {
-        let storage = Storage::new(config.db_path.clone());
+        let open = |path: PathBuf| -> Box<dyn StorageBackend> {
+            match config.source {
+                DataSource::Trackio => Box::new(Storage::new(path)),
+                DataSource::Parquet => Box::new(ParquetBackend::new(path)),
+                DataSource::Tensorboard => Box::new(TensorBoardBackend::new(path)),
+                DataSource::Mlflow => Box::new(MlflowBackend::new(path)),
+                DataSource::Logs => Box::new(LogFileBackend::new(path)),
+            }
+        };
+        let backend = match config.db_paths.as_slice() {
+            [path] => open(path.clone()),
+            paths => Box::new(MultiRootBackend::new(
+                paths.iter().map(|p| (p.clone(), open(p.clone()))).collect(),
+            )),
+        };
+        Ok(Self::with_backend(config, backend))
+    }
+
+    /// Create a new App instance reading from the given storage backend.
+    /// Data is loaded in the background; the first projects listing is requested immediately.
+    pub fn with_backend(config: AppConfig, backend: Box<dyn StorageBackend>) -> Self {
+        let loader = Loader::spawn(backend);
+        let initial_project = config.project.clone();
+        let media_preview = MediaPreview::new(config.image_protocol);
+        let resume_mode = config.resume_mode;
 
-        let mut app = App {
+        let app = App {
             config,
-            storage,
+            loader,
+            initial_project,
             projects: Vec::new(),
             runs: Vec::new(),
             metrics: Vec::new(),
             metric_names: Vec::new(),
+            system_metrics: Vec::new(),
+            system_run: None,
+            rich_values: RichValues::default(),
+            rich_run: None,
+            loaded_run: None,
             comparison: ComparisonState::new(),
+            diverged_runs: HashSet::new(),
             focused: FocusedPanel::Projects,
             selected_project: 0,
             selected_run: 0,
             metric_slot: MetricSlotState::new(),
             show_help: false,
+            show_system: false,
+            show_log: false,
+            resume_mode,
+            x_axis: XAxis::default(),
+            y_scales: HashMap::new(),
+            smoothing: Smoothing::default(),
+            chart_view: ChartView::new(),
+            step_cursor: None,
+            table_view: TableViewState::new(),
+            media_preview,
             config_panel: ConfigPanelState::new(),
             cached_config_lines: Vec::new(),
+            log_panel: ConfigPanelState::new(),
+            cached_log_lines: Vec::new(),
+            areas: Cell::new(PanelAreas::default()),
             last_refresh: Instant::now(),
             should_quit: false,
             error_message: None,
         };
 
         // Initial data load
-        app.load_projects()?;
+        app.loader.request(LoadRequest::Projects);
+
+        app
+    }
+
+    /// Id of the currently selected project, used for loading its data
+    fn selected_project_id(&self) -> Option<&str> {
+        self.projects
+            .get(self.selected_project)
+            .map(|p| p.id.as_str())
+    }
+
+    /// (project, run) key of the currently selected run
+    fn selected_run_key(&self) -> Option<(String, String)> {
+        let project = self.selected_project_id()?;
+        let run = self.runs.get(self.selected_run)?;
+        Some((project.to_string(), run.id.clone()))
+    }
+
+    /// Whether metrics for the selected run are still being loaded
+    fn is_loading_metrics(&self) -> bool {
+        let selected = self.selected_run_key();
+        selected.is_some() && selected != self.loaded_run
+    }
+
+    /// Request the runs of the selected project
+    fn request_runs(&self) {
+        if let Some(project) = self.selected_project_id() {
+            self.loader.request(LoadRequest::Runs {
+                project: project.to_string(),
+            });
+        }
+    }
+
+    /// Request metrics for the selected run.
+    /// If the run is unchanged since the last load, only newly logged points are fetched.
+    fn request_metrics(&self) {
+        if let Some(run_key) = self.selected_run_key() {
+            let incremental = self.loaded_run.as_ref() == Some(&run_key);
+            let (project, run_id) = run_key;
+            self.loader.request(LoadRequest::Metrics(MetricsRequest {
+                project,
+                run_id,
+                incremental,
+                selected: true,
+            }));
+        }
+    }
+
+    /// Request system metrics for the selected run, if the system panel is shown
+    fn request_system_metrics(&self) {
+        if !self.show_system {
+            return;
+        }
+        if let Some((project, run_id)) = self.selected_run_key() {
+            self.loader
+                .request(LoadRequest::SystemMetrics { project, run_id });
+        }
+    }
+
+    /// Request non-scalar values (histograms, ...) for the selected run.
+    /// If they are already loaded, only newly logged values are fetched.
+    fn request_rich_values(&self) {
+        if let Some(run_key) = self.selected_run_key() {
+            let incremental = self.rich_run.as_ref() == Some(&run_key);
+            let (project, run_id) = run_key;
+            self.loader.request(LoadRequest::RichValues {
+                project,
+                run_id,
+                incremental,
+            });
+        }
+    }
+
+    /// Request metrics for all comparison runs.
+    /// Runs that are already cached are refreshed incrementally.
+    fn request_comparison_metrics(&self) {
+        let Some(project) = self.selected_project_id() else {
+            return;
+        };
+        let selected_run_id = self.runs.get(self.selected_run).map(|r| r.id.as_str());
 
-        // If a project was specified, select it
-        if let Some(ref project_name) = app.config.project {
-            if let Some(idx) = app.projects.iter().position(|p| &p.name == project_name) {
-                app.selected_project = idx;
-                app.load_runs()?;
+        for run_id in self.comparison.marked_run_ids() {
+            // The selected run's copy is kept in sync by its own metrics responses
+            if selected_run_id == Some(run_id.as_str()) {
+                continue;
             }
-        } else if !app.projects.is_empty() {
-            app.load_runs()?;
+            self.loader.request(LoadRequest::Metrics(MetricsRequest {
+                project: project.to_string(),
+                run_id: run_id.clone(),
+                incremental: self.comparison.get_cached_metrics(run_id).is_some(),
+                selected: false,
+            }));
         }
+    }
+
+    /// Call after the selected project changes
+    fn on_project_changed(&mut self) {
+        // Clear comparison selection when changing projects
+        self.comparison.clear();
+        self.diverged_runs.clear();
+        self.runs.clear();
+        // Updates for the old project's runs are dropped from now on, so their
+        // storage cursors can't be trusted when coming back
+        self.loaded_run = None;
+        self.rich_run = None;
+        self.chart_view.reset();
+        self.config_panel.reset();
+        self.regenerate_config_lines();
+        self.request_runs();
+    }
+
+    /// Call after the selected run changes
+    fn on_run_changed(&mut self) {
+        // Updates for the previous run are dropped from now on while its
+        // storage cursor moves on, so coming back to it needs a full reload
+        self.loaded_run = None;
+        self.rich_run = None;
+        self.chart_view.reset();
+        self.config_panel.reset();
+        self.regenerate_config_lines();
+        self.log_panel.reset();
+        self.regenerate_log_lines();
+        self.step_cursor = None;
+        self.table_view.reset();
+        self.request_metrics();
+        self.request_system_metrics();
+        self.request_rich_values();
+    }
 
-        Ok(app)
+    /// Apply all completed background loads
+    pub fn process_load_responses(&mut self) {
+        while let Some(response) = self.loader.try_recv() {
+            let result = match response {
+                LoadResponse::Projects(result) => {
+                    result.map(|projects| self.set_projects(projects))
+                }
+                LoadResponse::Runs { project, result } => {
+                    result.map(|runs| self.set_runs(&project, runs))
+                }
+                LoadResponse::Metrics {
+                    project,
+                    run_id,
+                    result,
+                } => result.map(|update| self.apply_metrics_update(&project, &run_id, update)),
+                LoadResponse::SystemMetrics {
+                    project,
+                    run_id,
+                    result,
+                } => result.map(|metrics| {
+                    let run_key = (project, run_id);
+                    if self.selected_run_key().as_ref() == Some(&run_key) {
+                        self.system_metrics = metrics;
+                        self.system_run = Some(run_key);
+                    }
+                }),
+                LoadResponse::RichValues {
+                    project,
+                    run_id,
+                    result,
+                } => result.map(|update| self.apply_rich_values_update(project, run_id, update)),
+            };
+            if let Err(e) = result {
+                self.set_error(format!("Load error: {e:#}"));
+            }
+        }
     }
 
-    /// Load list of projects from storage
-    fn load_projects(&mut self) -> Result<()> {
-        self.projects = self.storage.list_projects()?;
+    /// Store a fresh projects listing and reload runs for the selected project
+    fn set_projects(&mut self, projects: Vec<Project>) {
+        self.projects = projects;
         if self.selected_project >= self.projects.len() {
             self.selected_project = self.projects.len().saturating_sub(1);
         }
-        Ok(())
-    }
 
-    /// Load runs for the currently selected project.
-    /// If `clear_comparison` is true, clears comparison state (used on project change).
-    fn load_runs_impl(&mut self, clear_comparison: bool) -> Result<()> {
+        // If a project was specified on the command line, select it on first load
+        if let Some(project_name) = self.initial_project.take() {
+            if let Some(idx) = self.projects.iter().position(|p| p.name == project_name) {
+                self.selected_project = idx;
+            }
+        }
+
         if self.projects.is_empty() {
             self.runs.clear();
             self.metrics.clear();
             self.metric_names.clear();
+            self.loaded_run = None;
             self.comparison.clear();
-            return Ok(());
+            return;
+        }
+
+        self.request_runs();
+    }
+
+    /// Store a fresh runs listing (if still for the selected project) and reload metrics
+    fn set_runs(&mut self, project: &str, runs: Vec<Run>) {
+        if self.selected_project_id() != Some(project) {
+            // Selection moved on while loading
+            return;
         }
 
-        let project = &self.projects[self.selected_project];
-        self.runs = self.storage.list_runs(&project.name)?;
+        let previous_run = self.selected_run_key();
+        self.runs = runs;
 
         if self.selected_run >= self.runs.len() {
             self.selected_run = self.runs.len().saturating_sub(1);
         }
 
-        if clear_comparison {
-            // Clear comparison selection when changing projects
-            self.comparison.clear();
-        } else {
-            // Prune any runs that no longer exist after refresh
-            let valid_ids: std::collections::HashSet<String> =
-                self.runs.iter().map(|r| r.id.clone()).collect();
-            self.comparison.prune_invalid_runs(&valid_ids);
+        // Prune any runs that no longer exist after refresh
+        let valid_ids: std::collections::HashSet<String> =
+            self.runs.iter().map(|r| r.id.clone()).collect();
+        self.comparison.prune_invalid_runs(&valid_ids);
+
+        // Runs with loaded metrics are flagged from their metrics, the rest from the listing
+        self.diverged_runs.retain(|id| valid_ids.contains(id));
+        let listed_flags: Vec<(String, bool)> = self
+            .runs
+            .iter()
+            .filter(|r| {
+                let loaded = self.loaded_run.as_ref().is_some_and(|(_, id)| id == &r.id);
+                !loaded && self.comparison.get_cached_metrics(&r.id).is_none()
+            })
+            .map(|r| (r.id.clone(), r.diverged))
+            .collect();
+        for (run_id, diverged) in listed_flags {
+            self.set_diverged(&run_id, diverged);
         }
 
-        // Load metrics for selected run
-        self.load_metrics()?;
+        if self.runs.is_empty() {
+            self.metrics.clear();
+            self.metric_names.clear();
+            self.loaded_run = None;
+            self.metric_slot.clamp(0);
+        } else if previous_run != self.selected_run_key() {
+            self.chart_view.reset();
+            self.config_panel.reset();
+        }
+        self.regenerate_config_lines();
 
-        Ok(())
+        self.request_metrics();
+        self.request_system_metrics();
+        self.request_rich_values();
+        self.request_comparison_metrics();
     }
 
-    /// Load runs for the currently selected project, clearing comparison state.
-    fn load_runs(&mut self) -> Result<()> {
-        self.load_runs_impl(true)
-    }
+    /// Apply loaded metrics to the selected run and/or its comparison cache
+    fn apply_metrics_update(&mut self, project: &str, run_id: &str, update: MetricsUpdate) {
+        if self.selected_project_id() != Some(project) {
+            // Selection moved on while loading
+            return;
+        }
+
+        // Storage tracks one incremental cursor per run, so every loaded copy of
+        // the run must receive each update to stay in sync
+        if self.comparison.marked_run_ids().contains(run_id) {
+            self.comparison.apply_update(run_id, update.clone());
+            if let Some(metrics) = self.comparison.get_cached_metrics(run_id) {
+                let diverged = has_diverged(metrics);
+                self.set_diverged(run_id, diverged);
+            }
+        }
 
-    /// Reload runs without clearing comparison state (for refresh).
-    fn reload_runs(&mut self) -> Result<()> {
-        self.load_runs_impl(false)
+        let run_key = (project.to_string(), run_id.to_string());
+        if self.selected_run_key().as_ref() != Some(&run_key) {
+            return;
+        }
+        match update {
+            MetricsUpdate::Reload(_) => {
+                update.apply(&mut self.metrics);
+                self.loaded_run = Some(run_key);
+            }
+            // New points only extend the series they were loaded on top of
+            MetricsUpdate::Append(_) if self.loaded_run.as_ref() == Some(&run_key) => {
+                if update.is_empty() {
+                    return;
+                }
+                update.apply(&mut self.metrics);
+            }
+            MetricsUpdate::Append(_) => return,
+        }
+        self.set_diverged(run_id, has_diverged(&self.metrics));
+        self.update_metric_names();
     }
 
-    /// Load metrics for the currently selected run
-    fn load_metrics(&mut self) -> Result<()> {
-        if self.runs.is_empty() {
-            self.metrics.clear();
-            self.metric_names.clear();
-            self.cached_config_lines.clear();
-            return Ok(());
+    /// Apply loaded non-scalar values to the selected run
+    fn apply_rich_values_update(
+        &mut self,
+        project: String,
+        run_id: String,
+        update: RichValuesUpdate,
+    ) {
+        let run_key = (project, run_id);
+        if self.selected_run_key().as_ref() != Some(&run_key) {
+            return;
         }
+        match update {
+            RichValuesUpdate::Reload(_) => self.rich_run = Some(run_key),
+            // New values only extend the series they were loaded on top of
+            RichValuesUpdate::Append(_) if self.rich_run.as_ref() == Some(&run_key) => {
+                if update.is_empty() {
+                    return;
+                }
+            }
+            RichValuesUpdate::Append(_) => return,
+        }
+        update.apply(&mut self.rich_values);
+        self.update_metric_names();
+        self.regenerate_log_lines();
+    }
 
-        let project = &self.projects[self.selected_project];
-        let run = &self.runs[self.selected_run];
+    /// Flag or unflag a run as diverged in the run list
+    fn set_diverged(&mut self, run_id: &str, diverged: bool) {
+        if diverged {
+            self.diverged_runs.insert(run_id.to_string());
+        } else {
+            self.diverged_runs.remove(run_id);
+        }
+    }
 
-        // Load all metrics data (single pass)
-        self.metrics = self.storage.get_all_metrics(&project.name, &run.id)?;
-        self.metric_names = self.metrics.iter().map(|m| m.name.clone()).collect();
+    /// Rebuild the metric selector entries: scalar metrics of the selected run,
+    /// then its histograms once loaded
+    fn update_metric_names(&mut self) {
+        // System metrics logged with training metrics go to the system panel instead
+        self.metric_names = self
+            .metrics
+            .iter()
+            .filter(|m| !m.is_system())
+            .map(|m| m.name.clone())
+            .collect();
+        if self.rich_run.is_some() && self.rich_run == self.loaded_run {
+            self.metric_names
+                .extend(self.rich_values.names().map(str::to_string));
+        }
 
         // Clamp metric slot state to valid range after metrics change
         self.metric_slot.clamp(self.metric_names.len());
+    }
 
-        // Regenerate cached config lines for the new run
-        self.regenerate_config_lines();
+    /// Apply a change to the metric slots. A locked y-range was picked for the
+    /// focused metric, so it is unlocked when another metric gets the focus.
+    fn change_metric_slot(&mut self, change: impl FnOnce(&mut MetricSlotState, usize)) {
+        let num_metrics = self.metric_names.len();
+        let focused = self.metric_slot.selected_metric(num_metrics);
+        change(&mut self.metric_slot, num_metrics);
+        if self.metric_slot.selected_metric(num_metrics) != focused {
+            self.chart_view.unlock_y();
+        }
+    }
 
-        Ok(())
+    /// Name in the focused metric slot, if it is a histogram or table of the selected run
+    fn focused_rich_name(&self) -> Option<&str> {
+        if self.rich_run.is_none() || self.rich_run != self.selected_run_key() {
+            return None;
+        }
+        let idx = self.metric_slot.selected_metric(self.metric_names.len());
+        let name = self.metric_names.get(idx)?;
+        self.rich_values.step_count(name).map(|_| name.as_str())
+    }
+
+    /// Histogram occupying the focused metric slot, if any
+    fn focused_histogram(&self) -> Option<&Histogram> {
+        self.rich_values.histogram(self.focused_rich_name()?)
+    }
+
+    /// Table occupying the focused metric slot, if any
+    fn focused_table(&self) -> Option<&Table> {
+        self.rich_values.table(self.focused_rich_name()?)
+    }
+
+    /// Media series occupying the focused metric slot, if any
+    fn focused_media(&self) -> Option<&Media> {
+        self.rich_values.media(self.focused_rich_name()?)
+    }
+
+    /// Move the histogram/table/media step cursor by `delta` steps
+    fn move_step_cursor(&mut self, delta: isize) {
+        let Some(len) = self
+            .focused_rich_name()
+            .and_then(|name| self.rich_values.step_count(name))
+        else {
+            return;
+        };
+        let last = len.saturating_sub(1);
+        let current = self.step_cursor.unwrap_or(last).min(last);
+        let next = current.saturating_add_signed(delta).min(last);
+        // Back at the latest step, keep following new steps
+        self.step_cursor = (next < last).then_some(next);
     }
 
     /// Refresh all data without clearing comparison state
-    fn refresh(&mut self) -> Result<()> {
+    fn refresh(&mut self) {
         self.error_message = None; // Clear any previous errors
-        self.load_projects()?;
-        self.reload_runs()?; // Use reload_runs to preserve comparison state
-        self.load_comparison_metrics()?;
+        self.loader.request(LoadRequest::Projects);
         self.last_refresh = Instant::now();
-        Ok(())
     }
 
     /// Set an error message to display (non-fatal)
@@ -244,126 +661,72 @@ impl App {
             .collect();
     }
 
-    /// Update search match indices based on current search query
-    fn update_config_search_matches(&mut self) {
-        self.config_panel.match_indices.clear();
-        if self.config_panel.search.is_empty() {
-            return;
-        }
-
-        let query = self.config_panel.search.to_lowercase();
-        for (idx, line) in self.cached_config_lines.iter().enumerate() {
-            if line.to_lowercase().contains(&query) {
-                self.config_panel.match_indices.push(idx);
-            }
-        }
-
-        // Reset current match if out of bounds
-        if self.config_panel.current_match >= self.config_panel.match_indices.len() {
-            self.config_panel.current_match = 0;
-        }
-    }
-
-    /// Jump to the next search match
-    fn next_config_match(&mut self) {
-        if self.config_panel.match_indices.is_empty() {
-            return;
-        }
-        self.config_panel.current_match =
-            (self.config_panel.current_match + 1) % self.config_panel.match_indices.len();
-        self.scroll_to_current_match();
-    }
-
-    /// Jump to the previous search match
-    fn prev_config_match(&mut self) {
-        if self.config_panel.match_indices.is_empty() {
-            return;
-        }
-        self.config_panel.current_match = self
-            .config_panel
-            .current_match
-            .checked_sub(1)
-            .unwrap_or(self.config_panel.match_indices.len() - 1);
-        self.scroll_to_current_match();
-    }
-
-    /// Scroll to make the current match visible
-    fn scroll_to_current_match(&mut self) {
-        if let Some(&line_idx) = self
-            .config_panel
-            .match_indices
-            .get(self.config_panel.current_match)
-        {
-            self.config_panel.scroll_v = line_idx as u16;
-        }
+    /// Regenerate cached log lines (call when text values load)
+    fn regenerate_log_lines(&mut self) {
+        self.cached_log_lines =
+            if self.rich_run.is_some() && self.rich_run == self.selected_run_key() {
+                log_lines(&self.rich_values, self.config.timezone)
+            } else {
+                Vec::new()
+            };
+        self.log_panel.update_matches(&self.cached_log_lines);
     }
 
-    /// Load/refresh metrics for all comparison runs into the cache
-    fn load_comparison_metrics(&mut self) -> Result<()> {
-        if self.projects.is_empty() || self.runs.is_empty() {
-            return Ok(());
-        }
-
-        let project = &self.projects[self.selected_project];
-
-        let marked_ids: Vec<String> = self.comparison.marked_run_ids().iter().cloned().collect();
-        for run_id in marked_ids {
-            if let Some(run) = self.runs.iter().find(|r| r.id == run_id) {
-                if let Ok(metrics) = self.storage.get_all_metrics(&project.name, &run.id) {
-                    self.comparison.cache_metrics(&run_id, metrics);
-                }
-            }
+    /// Scroll/search state and lines of the focused text panel (config or log)
+    fn focused_text_panel(&mut self) -> Option<(&mut ConfigPanelState, &[String])> {
+        match self.focused {
+            FocusedPanel::Config => Some((&mut self.config_panel, &self.cached_config_lines)),
+            FocusedPanel::Log => Some((&mut self.log_panel, &self.cached_log_lines)),
+            FocusedPanel::Projects | FocusedPanel::Runs | FocusedPanel::Chart => None,
         }
-
-        Ok(())
     }
 
     /// Load metrics for a single comparison run into the cache
-    fn load_single_comparison_run(&mut self, run_id: &str) -> Result<()> {
-        if self.projects.is_empty() {
-            return Ok(());
+    fn load_single_comparison_run(&mut self, run_id: &str) {
+        // The selected run is already loaded; copying avoids resetting its incremental cursor
+        if self.loaded_run.as_ref().map(|(_, id)| id.as_str()) == Some(run_id) {
+            self.comparison.cache_metrics(run_id, self.metrics.clone());
+            return;
         }
 
-        let project = &self.projects[self.selected_project];
-
-        if let Some(run) = self.runs.iter().find(|r| r.id == run_id) {
-            if let Ok(metrics) = self.storage.get_all_metrics(&project.name, &run.id) {
-                self.comparison.cache_metrics(run_id, metrics);
-            }
+        if let Some(project) = self.selected_project_id() {
+            self.loader.request(LoadRequest::Metrics(MetricsRequest {
+                project: project.to_string(),
+                run_id: run_id.to_string(),
+                incremental: false,
+                selected: false,
+            }));
         }
-
-        Ok(())
     }
 
     /// Handle keyboard input
     fn handle_input(&mut self, key: KeyCode, _modifiers: KeyModifiers) -> Result<()> {
         // Handle search input mode first
-        if self.config_panel.search_active {
-            match key {
-                KeyCode::Esc => {
-                    self.config_panel.search_active = false;
-                    return Ok(());
-                }
-                KeyCode::Enter => {
-                    self.config_panel.search_active = false;
-                    // Jump to first match if any
-                    if !self.config_panel.match_indices.is_empty() {
-                        self.config_panel.current_match = 0;
-                        self.scroll_to_current_match();
+        if let Some((panel, lines)) = self.focused_text_panel() {
+            if panel.search_active {
+                match key {
+                    KeyCode::Esc => {
+                        panel.search_active = false;
                     }
-                    return Ok(());
-                }
-                KeyCode::Backspace => {
-                    self.config_panel.search.pop();
-                    self.update_config_search_matches();
-                    return Ok(());
-                }
-                KeyCode::Char(c) => {
-                    self.config_panel.search.push(c);
-                    self.update_config_search_matches();
-                    return Ok(());
+                    KeyCode::Enter => {
+                        panel.search_active = false;
+                        // Jump to first match if any
+                        if !panel.match_indices.is_empty() {
+                            panel.current_match = 0;
+                            panel.scroll_to_current_match();
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        panel.search.pop();
+                        panel.update_matches(lines);
+                    }
+                    KeyCode::Char(c) => {
+                        panel.search.push(c);
+                        panel.update_matches(lines);
+                    }
+                    _ => {}
                 }
-                _ => return Ok(()),
+                return Ok(());
             }
         }
 
@@ -386,15 +749,58 @@ impl App {
                 return Ok(());
             }
             KeyCode::Char('r') => {
-                self.refresh()?;
+                self.refresh();
+                return Ok(());
+            }
+            KeyCode::Char('y') if !self.show_help => {
+                self.show_system = !self.show_system;
+                self.request_system_metrics();
+                return Ok(());
+            }
+            KeyCode::Char('l') if !self.show_help => {
+                self.show_log = !self.show_log;
+                if self.show_log {
+                    self.focused = FocusedPanel::Log;
+                } else if self.focused == FocusedPanel::Log {
+                    self.focused = FocusedPanel::Runs;
+                }
+                return Ok(());
+            }
+            KeyCode::Char('R') if !self.show_help => {
+                self.resume_mode = self.resume_mode.next();
+                return Ok(());
+            }
+            KeyCode::Char('x') if !self.show_help => {
+                self.x_axis = self.x_axis.next_time_axis();
+                self.chart_view.reset();
+                return Ok(());
+            }
+            KeyCode::Char('z') if !self.show_help => {
+                self.focused = if self.focused == FocusedPanel::Chart {
+                    FocusedPanel::Runs
+                } else {
+                    FocusedPanel::Chart
+                };
+                return Ok(());
+            }
+            KeyCode::Char('m') if !self.show_help => {
+                self.smoothing.next_mode();
+                return Ok(());
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') if !self.show_help => {
+                self.smoothing.adjust(1);
+                return Ok(());
+            }
+            KeyCode::Char('-') if !self.show_help => {
+                self.smoothing.adjust(-1);
                 return Ok(());
             }
             KeyCode::Tab => {
-                self.focused = self.focused.next();
+                self.focused = self.focused.next(self.show_log);
                 return Ok(());
             }
             KeyCode::BackTab => {
-                self.focused = self.focused.prev();
+                self.focused = self.focused.prev(self.show_log);
                 return Ok(());
             }
             _ => {}
@@ -409,33 +815,102 @@ impl App {
         if let KeyCode::Char(c) = key {
             if let Some(n) = c.to_digit(10) {
                 if n > 0 {
-                    self.metric_slot
-                        .select_slot((n as usize) - 1, self.metric_names.len());
+                    self.change_metric_slot(|slot, num_metrics| {
+                        slot.select_slot((n as usize) - 1, num_metrics)
+                    });
                     return Ok(());
                 }
             }
         }
 
-        // Shift metric window with arrow keys (except when Config panel is focused)
-        if self.focused != FocusedPanel::Config {
+        // Shift metric window with arrow keys (except when a text panel or the chart is focused)
+        if !matches!(
+            self.focused,
+            FocusedPanel::Config | FocusedPanel::Log | FocusedPanel::Chart
+        ) {
             match key {
                 KeyCode::Left => {
-                    self.metric_slot.shift_left(self.metric_names.len());
+                    self.change_metric_slot(MetricSlotState::shift_left);
                     return Ok(());
                 }
                 KeyCode::Right => {
-                    self.metric_slot.shift_right(self.metric_names.len());
+                    self.change_metric_slot(MetricSlotState::shift_right);
                     return Ok(());
                 }
                 _ => {}
             }
         }
 
-        // Toggle metric for overlay with Space
+        // Move the histogram/table/media step cursor
+        if key == KeyCode::Char('[') || key == KeyCode::Char(']') {
+            self.move_step_cursor(if key == KeyCode::Char('[') { -1 } else { 1 });
+            return Ok(());
+        }
+
+        // Scroll and sort the focused table
+        let table_size = self.focused_table().and_then(|table| {
+            let point = self.step_cursor.and_then(|i| table.points.get(i));
+            let point = point.or(table.points.last())?;
+            Some((point.rows.len(), point.columns.len()))
+        });
+        if let Some((num_rows, num_columns)) = table_size {
+            match key {
+                KeyCode::PageDown => {
+                    self.table_view.scroll_by(TABLE_PAGE_ROWS, num_rows);
+                    return Ok(());
+                }
+                KeyCode::PageUp => {
+                    self.table_view.scroll_by(-TABLE_PAGE_ROWS, num_rows);
+                    return Ok(());
+                }
+                KeyCode::Char('o') => {
+                    self.table_view.cycle_sort(num_columns);
+                    return Ok(());
+                }
+                KeyCode::Char('O') => {
+                    self.table_view.toggle_order();
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        // Toggle metric for overlay with Space (only scalar metrics can be overlaid)
         if key == KeyCode::Char(' ') {
             let metric_idx = self.metric_slot.selected_metric(self.metric_names.len());
             if let Some(name) = self.metric_names.get(metric_idx) {
-                self.metric_slot.toggle_metric(name);
+                if self.rich_values.step_count(name).is_none() {
+                    self.metric_slot.toggle_metric(name);
+                }
+            }
+            return Ok(());
+        }
+
+        // Cycle the y-axis scale of the focused scalar metric
+        if key == KeyCode::Char('L') {
+            let metric_idx = self.metric_slot.selected_metric(self.metric_names.len());
+            if let Some(name) = self.metric_names.get(metric_idx) {
+                if self.rich_values.step_count(name).is_none() {
+                    let scale = self.y_scales.entry(name.clone()).or_default();
+                    *scale = scale.next();
+                    self.chart_view.unlock_y();
+                }
+            }
+            return Ok(());
+        }
+
+        // Plot against the focused scalar metric (again to go back to steps)
+        if key == KeyCode::Char('X') {
+            let metric_idx = self.metric_slot.selected_metric(self.metric_names.len());
+            if let Some(name) = self.metric_names.get(metric_idx) {
+                if self.rich_values.step_count(name).is_none() {
+                    self.x_axis = if self.x_axis == XAxis::Metric(name.clone()) {
+                        XAxis::Step
+                    } else {
+                        XAxis::Metric(name.clone())
+                    };
+                    self.chart_view.reset();
+                }
             }
             return Ok(());
         }
@@ -459,7 +934,7 @@ impl App {
                 let was_added = self.comparison.toggle_run(&run_id);
                 if was_added {
                     // Load metrics for the newly added run
-                    self.load_single_comparison_run(&run_id)?;
+                    self.load_single_comparison_run(&run_id);
                 }
             }
             return Ok(());
@@ -469,7 +944,8 @@ impl App {
         match self.focused {
             FocusedPanel::Projects => self.handle_project_navigation(key)?,
             FocusedPanel::Runs => self.handle_run_navigation(key)?,
-            FocusedPanel::Config => self.handle_config_navigation(key)?,
+            FocusedPanel::Config | FocusedPanel::Log => self.handle_text_panel_navigation(key)?,
+            FocusedPanel::Chart => self.handle_chart_navigation(key),
         }
 
         Ok(())
@@ -477,22 +953,16 @@ impl App {
 
     fn handle_project_navigation(&mut self, key: KeyCode) -> Result<()> {
         match key {
-            KeyCode::Down => {
-                if !self.projects.is_empty() {
-                    self.selected_project = (self.selected_project + 1) % self.projects.len();
-                    self.config_panel.reset();
-                    self.load_runs()?;
-                }
+            KeyCode::Down if !self.projects.is_empty() => {
+                self.selected_project = (self.selected_project + 1) % self.projects.len();
+                self.on_project_changed();
             }
-            KeyCode::Up => {
-                if !self.projects.is_empty() {
-                    self.selected_project = self
-                        .selected_project
-                        .checked_sub(1)
-                        .unwrap_or(self.projects.len() - 1);
-                    self.config_panel.reset();
-                    self.load_runs()?;
-                }
+            KeyCode::Up if !self.projects.is_empty() => {
+                self.selected_project = self
+                    .selected_project
+                    .checked_sub(1)
+                    .unwrap_or(self.projects.len() - 1);
+                self.on_project_changed();
             }
             _ => {}
         }
@@ -501,22 +971,16 @@ impl App {
 
     fn handle_run_navigation(&mut self, key: KeyCode) -> Result<()> {
         match key {
-            KeyCode::Down => {
-                if !self.runs.is_empty() {
-                    self.selected_run = (self.selected_run + 1) % self.runs.len();
-                    self.config_panel.reset();
-                    self.load_metrics()?;
-                }
+            KeyCode::Down if !self.runs.is_empty() => {
+                self.selected_run = (self.selected_run + 1) % self.runs.len();
+                self.on_run_changed();
             }
-            KeyCode::Up => {
-                if !self.runs.is_empty() {
-                    self.selected_run = self
-                        .selected_run
-                        .checked_sub(1)
-                        .unwrap_or(self.runs.len() - 1);
-                    self.config_panel.reset();
-                    self.load_metrics()?;
-                }
+            KeyCode::Up if !self.runs.is_empty() => {
+                self.selected_run = self
+                    .selected_run
+                    .checked_sub(1)
+                    .unwrap_or(self.runs.len() - 1);
+                self.on_run_changed();
             }
             KeyCode::Esc => {
                 self.focused = FocusedPanel::Projects;
@@ -526,63 +990,165 @@ impl App {
         Ok(())
     }
 
-    fn handle_config_navigation(&mut self, key: KeyCode) -> Result<()> {
-        let config_len = self.current_config().len() as u16;
+    /// Handle mouse input: clicks focus panels and select projects, runs and
+    /// metric slots, the wheel scrolls, and on the chart dragging zooms into a
+    /// range and hovering moves the cursor
+    fn handle_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        if self.show_help {
+            return Ok(());
+        }
+        let areas = self.areas.get();
+        let (column, row) = (mouse.column, mouse.row);
+        let position = Position::new(column, row);
+        let panel = [
+            (FocusedPanel::Projects, areas.projects),
+            (FocusedPanel::Runs, areas.runs),
+            (FocusedPanel::Config, areas.config),
+            (FocusedPanel::Log, areas.log),
+            (FocusedPanel::Chart, areas.chart),
+        ]
+        .into_iter()
+        .find(|(_, area)| area.contains(position))
+        .map(|(panel, _)| panel);
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if areas.metric_selector.contains(position) {
+                    let selector = MetricSelector::new(&self.metric_names, &self.metric_slot);
+                    if let Some(slot) = selector.slot_at(column - areas.metric_selector.x) {
+                        self.change_metric_slot(|state, num_metrics| {
+                            state.select_slot(slot, num_metrics)
+                        });
+                    }
+                    return Ok(());
+                }
+                let Some(panel) = panel else {
+                    return Ok(());
+                };
+                self.focused = panel;
+                match panel {
+                    FocusedPanel::Projects => {
+                        let clicked = list_index_at(
+                            areas.projects,
+                            row,
+                            self.selected_project,
+                            self.projects.len(),
+                        );
+                        if let Some(idx) = clicked.filter(|&idx| idx != self.selected_project) {
+                            self.selected_project = idx;
+                            self.on_project_changed();
+                        }
+                    }
+                    FocusedPanel::Runs => {
+                        let clicked =
+                            list_index_at(areas.runs, row, self.selected_run, self.runs.len());
+                        if let Some(idx) = clicked.filter(|&idx| idx != self.selected_run) {
+                            self.selected_run = idx;
+                            self.on_run_changed();
+                        }
+                    }
+                    FocusedPanel::Chart => {
+                        self.chart_view.start_drag(column, row);
+                    }
+                    FocusedPanel::Config | FocusedPanel::Log => {}
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => self.chart_view.hover(column, row),
+            MouseEventKind::Up(MouseButton::Left) => self.chart_view.end_drag(column),
+            MouseEventKind::Moved if panel == Some(FocusedPanel::Chart) => {
+                self.chart_view.hover(column, row)
+            }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let down = mouse.kind == MouseEventKind::ScrollDown;
+                // Lists stop at their ends rather than wrapping around
+                let at_end = |selected: usize, len: usize| {
+                    if down {
+                        selected + 1 >= len
+                    } else {
+                        selected == 0
+                    }
+                };
+                let scrolls = match panel {
+                    Some(FocusedPanel::Projects) => {
+                        !at_end(self.selected_project, self.projects.len())
+                    }
+                    Some(FocusedPanel::Runs) => !at_end(self.selected_run, self.runs.len()),
+                    Some(FocusedPanel::Config | FocusedPanel::Log) => true,
+                    Some(FocusedPanel::Chart) | None => false,
+                };
+                if let (Some(panel), true) = (panel, scrolls) {
+                    self.focused = panel;
+                    let key = if down { KeyCode::Down } else { KeyCode::Up };
+                    self.handle_input(key, KeyModifiers::NONE)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Zoom, pan and move the cursor of the chart
+    fn handle_chart_navigation(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up => self.chart_view.zoom(true),
+            KeyCode::Down => self.chart_view.zoom(false),
+            KeyCode::Left => self.chart_view.pan(-1.0),
+            KeyCode::Right => self.chart_view.pan(1.0),
+            KeyCode::Char('a') => self.chart_view.toggle_y_lock(),
+            KeyCode::Char('0') => self.chart_view.reset(),
+            KeyCode::Char('c') => self.chart_view.toggle_cursor(),
+            KeyCode::Char(',') => self.chart_view.move_cursor(-1),
+            KeyCode::Char('.') => self.chart_view.move_cursor(1),
+            KeyCode::Esc => self.focused = FocusedPanel::Runs,
+            _ => {}
+        }
+    }
+
+    /// Scroll and search the focused config or log panel
+    fn handle_text_panel_navigation(&mut self, key: KeyCode) -> Result<()> {
+        let Some((panel, lines)) = self.focused_text_panel() else {
+            return Ok(());
+        };
+        let num_lines = lines.len() as u16;
 
         match key {
             // Vertical scrolling
-            KeyCode::Down => {
-                if config_len > 0 {
-                    self.config_panel.scroll_v = self
-                        .config_panel
-                        .scroll_v
-                        .saturating_add(1)
-                        .min(config_len.saturating_sub(1));
-                }
+            KeyCode::Down if num_lines > 0 => {
+                panel.scroll_v = panel
+                    .scroll_v
+                    .saturating_add(1)
+                    .min(num_lines.saturating_sub(1));
             }
             KeyCode::Up => {
-                self.config_panel.scroll_v = self.config_panel.scroll_v.saturating_sub(1);
+                panel.scroll_v = panel.scroll_v.saturating_sub(1);
             }
             // Horizontal scrolling
             KeyCode::Right => {
-                self.config_panel.scroll_h = self
-                    .config_panel
-                    .scroll_h
-                    .saturating_add(HORIZONTAL_SCROLL_STEP);
+                panel.scroll_h = panel.scroll_h.saturating_add(HORIZONTAL_SCROLL_STEP);
             }
             KeyCode::Left => {
-                self.config_panel.scroll_h = self
-                    .config_panel
-                    .scroll_h
-                    .saturating_sub(HORIZONTAL_SCROLL_STEP);
+                panel.scroll_h = panel.scroll_h.saturating_sub(HORIZONTAL_SCROLL_STEP);
             }
             // Search
             KeyCode::Char('/') => {
-                self.config_panel.search_active = true;
-                self.config_panel.search.clear();
-                self.config_panel.match_indices.clear();
-                self.config_panel.current_match = 0;
+                panel.start_search();
             }
             // Navigate matches
             KeyCode::Char('n') => {
-                self.next_config_match();
+                panel.next_match();
             }
             KeyCode::Char('N') => {
-                self.prev_config_match();
+                panel.prev_match();
             }
             // Clear search
             KeyCode::Char('c') => {
-                self.config_panel.search.clear();
-                self.config_panel.match_indices.clear();
-                self.config_panel.current_match = 0;
+                panel.clear_search();
             }
             // Exit to previous panel
             KeyCode::Esc => {
-                if !self.config_panel.search.is_empty() {
+                if !panel.search.is_empty() {
                     // First Esc clears search
-                    self.config_panel.search.clear();
-                    self.config_panel.match_indices.clear();
-                    self.config_panel.current_match = 0;
+                    panel.clear_search();
                 } else {
                     self.focused = FocusedPanel::Runs;
                 }
@@ -592,6 +1158,22 @@ impl App {
         Ok(())
     }
 
+    /// Render the selected run's system metrics: `system/` series logged with the
+    /// training metrics plus any loaded from the backend's separate system data
+    fn render_system_panel(&self, frame: &mut ratatui::Frame, area: Rect) {
+        let loading = self.is_loading_metrics();
+        let mut system: Vec<&Metric> = Vec::new();
+        if !loading {
+            system.extend(self.metrics.iter().filter(|m| m.is_system()));
+        }
+        if self.system_run.is_some() && self.system_run == self.selected_run_key() {
+            system.extend(self.system_metrics.iter());
+        }
+        system.sort_by(|a, b| a.name.cmp(&b.name));
+
+        SystemPanel::new(&system, loading).render(frame, area);
+    }
+
     /// Render the UI
     fn render(&self, frame: &mut ratatui::Frame) {
         let size = frame.area();
@@ -624,12 +1206,17 @@ impl App {
             ])
             .split(body_chunks[0]);
 
-        // Content layout: chart and metric selector
+        // Content layout: chart, metric selector and the optional log panel
         let content_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(10),   // Chart
                 Constraint::Length(1), // Metric selector
+                if self.show_log {
+                    Constraint::Percentage(LOG_PANEL_PERCENT) // Log panel
+                } else {
+                    Constraint::Length(0)
+                },
             ])
             .split(body_chunks[1]);
 
@@ -645,6 +1232,7 @@ impl App {
             &self.runs,
             self.selected_run,
             self.comparison.marked_run_ids(),
+            &self.diverged_runs,
         );
         run_list.render(frame, sidebar_chunks[1], self.focused == FocusedPanel::Runs);
 
@@ -721,10 +1309,14 @@ impl App {
             .map(|r| r.display_name.clone())
             .unwrap_or_default();
 
-        for (i, (metric_name, _)) in metrics_to_show.iter().enumerate() {
-            // Use position in metrics_to_show for marker assignment (i), not the run-specific index
-            if let Some(metric) = self.metrics.iter().find(|m| m.name == *metric_name) {
-                chart_metrics.push((run_name.clone(), self.selected_run, i, metric));
+        // While a newly selected run loads, `metrics` still belongs to the previous run
+        let loading = self.is_loading_metrics();
+        if !loading {
+            for (i, (metric_name, _)) in metrics_to_show.iter().enumerate() {
+                // Use position in metrics_to_show for marker assignment (i), not the run-specific index
+                if let Some(metric) = self.metrics.iter().find(|m| m.name == *metric_name) {
+                    chart_metrics.push((run_name.clone(), self.selected_run, i, metric));
+                }
             }
         }
 
@@ -750,29 +1342,115 @@ impl App {
             (is_focused_run_unselected, *metric_idx, *run_idx)
         });
 
-        let chart = MetricsChart::new(&chart_metrics, &chart_title);
-        chart.render(frame, content_chunks[0]);
+        // Axis data of each shown run, for non-step x-axes
+        let mut run_axes: HashMap<usize, RunAxis> = HashMap::new();
+        if !loading {
+            run_axes.insert(self.selected_run, RunAxis::new(&self.metrics, &self.x_axis));
+        }
+        for run_id in self.comparison.marked_run_ids() {
+            let Some(metrics) = self.comparison.get_cached_metrics(run_id) else {
+                continue;
+            };
+            if let Some(run_idx) = self.runs.iter().position(|r| &r.id == run_id) {
+                run_axes
+                    .entry(run_idx)
+                    .or_insert_with(|| RunAxis::new(metrics, &self.x_axis));
+            }
+        }
+
+        // Optional system panel to the right of the chart
+        let chart_area = if self.show_system {
+            let chart_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Min(40),                          // Chart
+                    Constraint::Percentage(SYSTEM_PANEL_PERCENT), // System panel
+                ])
+                .split(content_chunks[0]);
+            self.render_system_panel(frame, chart_chunks[1]);
+            chart_chunks[0]
+        } else {
+            content_chunks[0]
+        };
+        self.areas.set(PanelAreas {
+            projects: sidebar_chunks[0],
+            runs: sidebar_chunks[1],
+            config: sidebar_chunks[2],
+            log: content_chunks[2],
+            chart: chart_area,
+            metric_selector: content_chunks[1],
+        });
+
+        if let Some(histogram) = self.focused_histogram() {
+            let cursor = self
+                .step_cursor
+                .unwrap_or(histogram.points.len().saturating_sub(1));
+            HistogramView::new(histogram, cursor).render(frame, chart_area);
+        } else if let Some(table) = self.focused_table() {
+            let cursor = self
+                .step_cursor
+                .unwrap_or(table.points.len().saturating_sub(1));
+            TableView::new(table, cursor, &self.table_view).render(frame, chart_area);
+        } else if let Some(media) = self.focused_media() {
+            let cursor = self
+                .step_cursor
+                .unwrap_or(media.points.len().saturating_sub(1));
+            MediaView::new(media, cursor, &self.media_preview).render(frame, chart_area);
+        } else {
+            let chart = MetricsChart::new(&chart_metrics, &chart_title)
+                .loading(loading)
+                .resume_mode(self.resume_mode)
+                .x_axis(self.x_axis.clone(), run_axes)
+                .timezone(self.config.timezone)
+                .formats(&self.config.formats)
+                .smoothing(self.smoothing)
+                .view(&self.chart_view)
+                .focused_run(self.selected_run)
+                .focused(self.focused == FocusedPanel::Chart)
+                .y_scale(
+                    focused_metric_name
+                        .as_ref()
+                        .and_then(|name| self.y_scales.get(name))
+                        .copied()
+                        .unwrap_or_default(),
+                );
+            chart.render(frame, chart_area);
+        }
 
         // Render metric selector
         let metric_selector = MetricSelector::new(&self.metric_names, &self.metric_slot);
         metric_selector.render(frame, content_chunks[1]);
 
+        if self.show_log {
+            let log_panel = LogPanel::new(&self.cached_log_lines, &self.log_panel);
+            log_panel.render(frame, content_chunks[2], self.focused == FocusedPanel::Log);
+        }
+
         // Render status bar
         let project_name = self
             .projects
             .get(self.selected_project)
-            .map(|p| p.name.as_str());
+            .map(|p| p.qualified_name());
         let error_msg = self.error_message.as_deref();
-        let status_bar = StatusBar::new(project_name, error_msg);
+        let status_bar = StatusBar::new(project_name.as_deref(), error_msg);
         status_bar.render(frame, main_chunks[1]);
 
         // Render help overlay if active
         if self.show_help {
+            // Terminal graphics would be drawn on top of the overlay
+            self.media_preview.cancel_graphics();
             HelpOverlay::new().render(frame, size);
         }
     }
 }
 
+/// Whether the latest value of any training metric is NaN or infinite
+fn has_diverged(metrics: &[Metric]) -> bool {
+    metrics
+        .iter()
+        .any(|m| !m.is_system() && m.latest_is_non_finite())
+}
+
 /// RAII guard for terminal cleanup.
 /// Ensures terminal is restored to normal state even on panic.
 struct TerminalGuard {
@@ -799,10 +1477,11 @@ impl Drop for TerminalGuard {
 
 /// Run the TUI application
 pub fn run(config: AppConfig) -> Result<()> {
-    // Check if database exists
-    let db_path = &config.db_path;
-    if !db_path.exists() {
-        eprintln!("No trackio data found at: {db_path:?}");
+    // Check if any data root exists
+    if !config.db_paths.iter().any(|p| p.exists()) {
+        for db_path in &config.db_paths {
+            eprintln!("No trackio data found at: {db_path:?}");
+        }
         eprintln!(
             "Run some experiments with trackio first, or specify a different path with --db-path"
         );
@@ -818,8 +1497,21 @@ pub fn run(config: AppConfig) -> Result<()> {
     let mut app = App::new(config).context("Failed to initialize application")?;
     let tick_rate = Duration::from_secs(app.config.refresh_interval_secs);
 
+    // Optional file watcher; if it cannot be set up we keep polling on the timer
+    let watcher = if app.config.watch {
+        match DbWatcher::new(&app.config.db_paths, app.config.source) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                app.set_error(format!("{e:#}; refreshing every {}s", tick_rate.as_secs()));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     // Main loop - guard ensures cleanup even on panic
-    let result = run_main_loop(&mut terminal, &mut app, tick_rate);
+    let result = run_main_loop(&mut terminal, &mut app, tick_rate, watcher);
 
     terminal.show_cursor().ok();
 
@@ -831,27 +1523,55 @@ fn run_main_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     tick_rate: Duration,
+    mut watcher: Option<DbWatcher>,
 ) -> Result<()> {
     loop {
+        // Pick up data loaded in the background since the last frame
+        app.process_load_responses();
+
         // Render - if this fails, we should exit
         terminal.draw(|f| app.render(f))?;
+        // Images shown with kitty/sixel graphics are written past the cell buffer
+        if app.media_preview.flush(terminal.backend_mut())? {
+            terminal.clear()?;
+            terminal.draw(|f| app.render(f))?;
+            app.media_preview.flush(terminal.backend_mut())?;
+        }
 
-        // Check if it's time to refresh (ignore refresh errors, just continue)
-        if app.last_refresh.elapsed() >= tick_rate {
-            if let Err(e) = app.refresh() {
-                // Log error but don't crash
-                app.set_error(format!("Refresh error: {e}"));
-            }
+        // Refresh on database changes when watching, otherwise on the timer
+        let should_refresh = match &watcher {
+            Some(w) => match w.has_changes() {
+                Ok(changed) => changed,
+                Err(e) => {
+                    // Watcher is unreliable from now on, fall back to polling
+                    watcher = None;
+                    app.set_error(format!("{e:#}; refreshing every {}s", tick_rate.as_secs()));
+                    false
+                }
+            },
+            None => app.last_refresh.elapsed() >= tick_rate,
+        };
+
+        // Ignore refresh errors, just continue
+        if should_refresh {
+            app.refresh();
         }
 
         // Handle input with timeout
-        let timeout = tick_rate.saturating_sub(app.last_refresh.elapsed());
+        let timeout = if watcher.is_some() {
+            Duration::from_millis(100)
+        } else {
+            tick_rate.saturating_sub(app.last_refresh.elapsed())
+        };
         if event::poll(timeout.min(Duration::from_millis(100)))? {
-            if let Event::Key(key) = event::read()? {
-                if let Err(e) = app.handle_input(key.code, key.modifiers) {
-                    // Log error but don't crash
-                    app.set_error(format!("Input error: {e}"));
-                }
+            let result = match event::read()? {
+                Event::Key(key) => app.handle_input(key.code, key.modifiers),
+                Event::Mouse(mouse) => app.handle_mouse(mouse),
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                // Log error but don't crash
+                app.set_error(format!("Input error: {e}"));
             }
         }
 
@@ -860,3 +1580,608 @@ fn run_main_loop(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{DisplayTimezone, ImageProtocol};
+    use crate::data::{
+        ConfigValue, HistogramPoint, MemoryBackend, MetricPoint, TablePoint, TextPoint, TextSeries,
+    };
+    use std::path::PathBuf;
+
+    fn test_config(project: Option<&str>) -> AppConfig {
+        AppConfig {
+            project: project.map(str::to_string),
+            refresh_interval_secs: 2,
+            db_paths: vec![PathBuf::new()],
+            watch: false,
+            source: DataSource::default(),
+            image_protocol: ImageProtocol::Braille,
+            resume_mode: ResumeMode::Latest,
+            timezone: DisplayTimezone::Local,
+            formats: Vec::new(),
+        }
+    }
+
+    fn make_metric(name: &str, values: &[f64]) -> Metric {
+        Metric::with_points(
+            name.to_string(),
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| MetricPoint {
+                    step: i as i64,
+                    value: v,
+                    timestamp: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn make_backend() -> MemoryBackend {
+        let run = |project: &str, id: &str| {
+            Run::new(id.to_string(), project.to_string(), None, Vec::new())
+        };
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_run(run("alpha", "a-0"), vec![make_metric("loss", &[1.0, 0.5])])
+            .add_run(run("beta", "b-0"), vec![make_metric("loss", &[2.0])])
+            .add_run(
+                run("beta", "b-1"),
+                vec![make_metric("acc", &[0.1]), make_metric("loss", &[3.0])],
+            );
+        backend
+    }
+
+    /// Apply background loads until `done` holds (or fail after a timeout)
+    fn wait_until(app: &mut App, done: impl Fn(&App) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            app.process_load_responses();
+            if done(app) {
+                return;
+            }
+            assert!(Instant::now() < deadline, "timed out waiting for loads");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_initial_load_selects_requested_project() {
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        assert_eq!(app.selected_project_id(), Some("beta"));
+        assert_eq!(app.runs.len(), 2);
+        assert_eq!(
+            app.loaded_run,
+            Some(("beta".to_string(), "b-0".to_string()))
+        );
+        assert_eq!(app.metric_names, vec!["loss"]);
+    }
+
+    #[test]
+    fn test_run_navigation_loads_in_background() {
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        app.focused = FocusedPanel::Runs;
+        app.handle_input(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        assert!(app.is_loading_metrics());
+
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        assert_eq!(app.metric_names, vec!["acc", "loss"]);
+    }
+
+    #[test]
+    fn test_stale_metrics_response_is_dropped() {
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        // A late response for a run that is no longer selected
+        app.apply_metrics_update(
+            "beta",
+            "b-1",
+            MetricsUpdate::Reload(vec![make_metric("stale", &[0.0])]),
+        );
+        assert_eq!(app.metric_names, vec!["loss"]);
+    }
+
+    #[test]
+    fn test_returning_to_run_reloads_it() {
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        // New points of b-0 arriving after switching away are dropped...
+        app.focused = FocusedPanel::Runs;
+        app.handle_input(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        app.apply_metrics_update(
+            "beta",
+            "b-0",
+            MetricsUpdate::Append(vec![make_metric("loss", &[9.0])]),
+        );
+        // ...so coming back requests all of its points again
+        app.handle_input(KeyCode::Up, KeyModifiers::NONE).unwrap();
+        assert_eq!(app.loaded_run, None);
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        assert_eq!(app.metric_names, vec!["loss"]);
+    }
+
+    #[test]
+    fn test_comparison_run_metrics_are_cached() {
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        app.focused = FocusedPanel::Runs;
+        app.handle_input(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        app.handle_input(KeyCode::Char('s'), KeyModifiers::NONE)
+            .unwrap();
+        app.handle_input(KeyCode::Up, KeyModifiers::NONE).unwrap();
+        app.handle_input(KeyCode::Char('s'), KeyModifiers::NONE)
+            .unwrap();
+
+        wait_until(&mut app, |app| {
+            app.comparison.get_cached_metrics("b-0").is_some()
+                && app.comparison.get_cached_metrics("b-1").is_some()
+        });
+        assert_eq!(app.comparison.get_cached_metrics("b-1").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_unsupported_schema_shown_in_status() {
+        let dir = tempfile::tempdir().unwrap();
+        rusqlite::Connection::open(dir.path().join("future.db"))
+            .unwrap()
+            .execute_batch("CREATE TABLE events (id INTEGER PRIMARY KEY, payload TEXT);")
+            .unwrap();
+
+        let mut app = App::with_backend(
+            test_config(None),
+            Box::new(Storage::new(dir.path().to_path_buf())),
+        );
+        wait_until(&mut app, |app| app.error_message.is_some());
+
+        let error = app.error_message.as_deref().unwrap();
+        assert!(error.contains("Unsupported trackio schema"), "{error}");
+    }
+
+    #[test]
+    fn test_system_metrics_kept_out_of_metric_slots() {
+        let mut backend = MemoryBackend::new();
+        backend.add_run(
+            Run::new("r".to_string(), "p".to_string(), None, Vec::new()),
+            vec![
+                make_metric("loss", &[1.0]),
+                make_metric("system/cpu", &[50.0]),
+            ],
+        );
+        let mut app = App::with_backend(test_config(None), Box::new(backend));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        assert_eq!(app.metric_names, vec!["loss"]);
+
+        app.handle_input(KeyCode::Char('y'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(app.show_system);
+        wait_until(&mut app, |app| app.system_run.is_some());
+        assert!(app.system_metrics.is_empty());
+    }
+
+    #[test]
+    fn test_histograms_listed_after_scalar_metrics() {
+        let mut backend = MemoryBackend::new();
+        backend.add_run(
+            Run::new("r".to_string(), "p".to_string(), None, Vec::new()),
+            vec![make_metric("loss", &[1.0])],
+        );
+        let points = (0..3)
+            .map(|step| HistogramPoint {
+                step,
+                timestamp: None,
+                edges: vec![0.0, 1.0],
+                counts: vec![1.0],
+            })
+            .collect();
+        backend.set_rich_values(
+            "p",
+            "r",
+            RichValues {
+                histograms: vec![Histogram {
+                    name: "weights".to_string(),
+                    points,
+                }],
+                ..Default::default()
+            },
+        );
+        let mut app = App::with_backend(test_config(None), Box::new(backend));
+        wait_until(&mut app, |app| app.metric_names.len() == 2);
+        assert_eq!(app.metric_names, vec!["loss", "weights"]);
+
+        // Histograms open in their own view rather than the overlay
+        app.handle_input(KeyCode::Char('2'), KeyModifiers::NONE)
+            .unwrap();
+        app.handle_input(KeyCode::Char(' '), KeyModifiers::NONE)
+            .unwrap();
+        assert!(app.metric_slot.selected_metric_names().is_empty());
+        assert!(app.focused_histogram().is_some());
+
+        // The step cursor starts at the latest step and stops at the first
+        for _ in 0..3 {
+            app.handle_input(KeyCode::Char('['), KeyModifiers::NONE)
+                .unwrap();
+        }
+        assert_eq!(app.step_cursor, Some(0));
+        app.handle_input(KeyCode::Char(']'), KeyModifiers::NONE)
+            .unwrap();
+        app.handle_input(KeyCode::Char(']'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.step_cursor, None);
+    }
+
+    #[test]
+    fn test_table_view_step_picker_and_sort() {
+        let mut backend = MemoryBackend::new();
+        backend.add_run(
+            Run::new("r".to_string(), "p".to_string(), None, Vec::new()),
+            vec![make_metric("loss", &[1.0])],
+        );
+        let points = (0..2)
+            .map(|step| TablePoint {
+                step,
+                timestamp: None,
+                columns: vec!["text".to_string(), "score".to_string()],
+                rows: vec![
+                    vec![ConfigValue::String("a".to_string()), ConfigValue::Int(2)],
+                    vec![ConfigValue::String("b".to_string()), ConfigValue::Int(1)],
+                ],
+            })
+            .collect();
+        backend.set_rich_values(
+            "p",
+            "r",
+            RichValues {
+                tables: vec![Table {
+                    name: "samples".to_string(),
+                    points,
+                }],
+                ..Default::default()
+            },
+        );
+        let mut app = App::with_backend(test_config(None), Box::new(backend));
+        wait_until(&mut app, |app| app.metric_names.len() == 2);
+        app.handle_input(KeyCode::Char('2'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(app.focused_table().is_some());
+
+        app.handle_input(KeyCode::Char('['), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.step_cursor, Some(0));
+        app.handle_input(KeyCode::Char('o'), KeyModifiers::NONE)
+            .unwrap();
+        app.handle_input(KeyCode::Char('o'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.table_view.sort_column, Some(1));
+        app.handle_input(KeyCode::PageDown, KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.table_view.scroll, 1);
+
+        // Switching run starts over at the latest version, unsorted
+        app.on_run_changed();
+        assert_eq!(app.step_cursor, None);
+        assert_eq!(app.table_view.sort_column, None);
+    }
+
+    #[test]
+    fn test_log_panel_search() {
+        let mut backend = MemoryBackend::new();
+        backend.add_run(
+            Run::new("r".to_string(), "p".to_string(), None, Vec::new()),
+            vec![make_metric("loss", &[1.0])],
+        );
+        let points = ["warming up", "training", "done training"]
+            .iter()
+            .enumerate()
+            .map(|(step, text)| TextPoint {
+                step: step as i64,
+                timestamp: None,
+                text: text.to_string(),
+            })
+            .collect();
+        backend.set_rich_values(
+            "p",
+            "r",
+            RichValues {
+                texts: vec![TextSeries {
+                    name: "status".to_string(),
+                    points,
+                }],
+                ..Default::default()
+            },
+        );
+        let mut app = App::with_backend(test_config(None), Box::new(backend));
+        wait_until(&mut app, |app| app.cached_log_lines.len() == 3);
+        // Text stays out of the metric slots
+        assert_eq!(app.metric_names, vec!["loss"]);
+
+        app.handle_input(KeyCode::Char('l'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(app.show_log);
+        assert_eq!(app.focused, FocusedPanel::Log);
+        for key in ['/', 't', 'r', 'a', 'i', 'n'] {
+            app.handle_input(KeyCode::Char(key), KeyModifiers::NONE)
+                .unwrap();
+        }
+        app.handle_input(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.log_panel.match_indices, vec![1, 2]);
+        assert_eq!(app.log_panel.scroll_v, 1);
+        app.handle_input(KeyCode::Char('n'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.log_panel.scroll_v, 2);
+
+        // The config panel keeps its own search
+        assert!(app.config_panel.search.is_empty());
+
+        // Hiding the panel moves focus back to the runs
+        app.handle_input(KeyCode::Char('l'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.focused, FocusedPanel::Runs);
+        assert_eq!(app.focused.next(false), FocusedPanel::Config);
+        assert_eq!(FocusedPanel::Config.next(false), FocusedPanel::Projects);
+    }
+
+    #[test]
+    fn test_diverged_runs_flagged() {
+        let run = |id: &str| Run::new(id.to_string(), "p".to_string(), None, Vec::new());
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_run(run("ok"), vec![make_metric("loss", &[1.0, f64::NAN, 0.5])])
+            .add_run(run("nan"), vec![make_metric("loss", &[1.0, f64::NAN])]);
+        let mut app = App::with_backend(test_config(None), Box::new(backend));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+        // Flagged from the run listing, before its metrics are loaded
+        assert_eq!(app.diverged_runs, HashSet::from(["nan".to_string()]));
+
+        app.focused = FocusedPanel::Runs;
+        let target = if app.runs[0].id == "nan" {
+            KeyCode::Up
+        } else {
+            KeyCode::Down
+        };
+        app.handle_input(target, KeyModifiers::NONE).unwrap();
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        assert_eq!(app.diverged_runs, HashSet::from(["nan".to_string()]));
+    }
+
+    #[test]
+    fn test_x_axis_selection() {
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        app.handle_input(KeyCode::Char('x'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.x_axis, XAxis::WallTime);
+        app.handle_input(KeyCode::Char('x'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.x_axis, XAxis::RelativeTime);
+
+        // The focused metric becomes the x-axis, and again goes back to steps
+        app.handle_input(KeyCode::Char('X'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.x_axis, XAxis::Metric("loss".to_string()));
+        app.handle_input(KeyCode::Char('X'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.x_axis, XAxis::Step);
+    }
+
+    #[test]
+    fn test_y_scale_remembered_per_metric() {
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+
+        app.handle_input(KeyCode::Char('L'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.y_scales.get("loss"), Some(&YScale::Log));
+        app.handle_input(KeyCode::Char('L'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.y_scales.get("loss"), Some(&YScale::Symlog));
+
+        // Kept when switching run
+        app.focused = FocusedPanel::Runs;
+        app.handle_input(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        assert_eq!(app.y_scales.get("loss"), Some(&YScale::Symlog));
+        assert_eq!(app.y_scales.len(), 1);
+    }
+
+    #[test]
+    fn test_chart_zoom_keys() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::with_backend(test_config(Some("alpha")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut draw = |app: &App| -> String {
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let buffer = terminal.backend().buffer();
+            buffer.content.iter().map(|cell| cell.symbol()).collect()
+        };
+        draw(&app);
+
+        app.handle_input(KeyCode::Char('z'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.focused, FocusedPanel::Chart);
+        app.handle_input(KeyCode::Up, KeyModifiers::NONE).unwrap();
+        app.handle_input(KeyCode::Char('a'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(draw(&app).contains("zoom ×2 · y locked"));
+
+        // Kept across refreshes, until reset
+        app.refresh();
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        assert!(draw(&app).contains("zoom ×2"));
+        app.handle_input(KeyCode::Char('0'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(!draw(&app).contains("zoom"));
+
+        app.handle_input(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+        assert_eq!(app.focused, FocusedPanel::Runs);
+    }
+
+    #[test]
+    fn test_chart_view_follows_selection() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut draw = |app: &App| -> String {
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let buffer = terminal.backend().buffer();
+            buffer.content.iter().map(|cell| cell.symbol()).collect()
+        };
+        draw(&app);
+        for key in ['z', 'a'] {
+            app.handle_input(KeyCode::Char(key), KeyModifiers::NONE)
+                .unwrap();
+        }
+        assert!(draw(&app).contains("y locked"));
+
+        // Another run starts with a fitted view
+        app.handle_input(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+        app.handle_input(KeyCode::Down, KeyModifiers::NONE).unwrap();
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        assert_eq!(app.runs[app.selected_run].id, "b-1");
+        assert!(!draw(&app).contains("y locked"));
+
+        // Another focused metric unlocks the y-range
+        for key in ['z', 'a'] {
+            app.handle_input(KeyCode::Char(key), KeyModifiers::NONE)
+                .unwrap();
+        }
+        assert!(draw(&app).contains("y locked"));
+        app.handle_input(KeyCode::Char('1'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(draw(&app).contains("y locked"));
+        app.handle_input(KeyCode::Char('2'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(!draw(&app).contains("y locked"));
+    }
+
+    #[test]
+    fn test_chart_cursor_readout() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::with_backend(test_config(Some("alpha")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut draw = |app: &App| -> String {
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let buffer = terminal.backend().buffer();
+            buffer.content.iter().map(|cell| cell.symbol()).collect()
+        };
+        draw(&app);
+
+        // The cursor starts at the last step and snaps to logged steps
+        for key in ['z', 'c'] {
+            app.handle_input(KeyCode::Char(key), KeyModifiers::NONE)
+                .unwrap();
+        }
+        let rendered = draw(&app);
+        assert!(rendered.contains("step 1"));
+        assert!(rendered.contains("0.5000"));
+        for key in [',', ','] {
+            app.handle_input(KeyCode::Char(key), KeyModifiers::NONE)
+                .unwrap();
+        }
+        let rendered = draw(&app);
+        assert!(rendered.contains("step 0"));
+        assert!(rendered.contains("1.000"));
+
+        app.handle_input(KeyCode::Char('c'), KeyModifiers::NONE)
+            .unwrap();
+        assert!(!draw(&app).contains("Δ focused"));
+    }
+
+    #[test]
+    fn test_mouse_selects_and_zooms() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App::with_backend(test_config(Some("beta")), Box::new(make_backend()));
+        wait_until(&mut app, |app| app.loaded_run.is_some());
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut draw = |app: &App| -> String {
+            terminal.draw(|frame| app.render(frame)).unwrap();
+            let buffer = terminal.backend().buffer();
+            buffer.content.iter().map(|cell| cell.symbol()).collect()
+        };
+        draw(&app);
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let click = MouseEventKind::Down(MouseButton::Left);
+
+        // Second row of the runs list
+        let runs = app.areas.get().runs;
+        app.handle_mouse(mouse(click, runs.x + 2, runs.y + 2))
+            .unwrap();
+        assert_eq!(app.focused, FocusedPanel::Runs);
+        assert_eq!(app.selected_run, 1);
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        draw(&app);
+
+        // The wheel stops at the end of the list
+        app.handle_mouse(mouse(MouseEventKind::ScrollDown, runs.x + 2, runs.y + 2))
+            .unwrap();
+        assert_eq!(app.selected_run, 1);
+        app.handle_mouse(mouse(MouseEventKind::ScrollUp, runs.x + 2, runs.y + 2))
+            .unwrap();
+        assert_eq!(app.selected_run, 0);
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+
+        // Back to b-1 and its second metric slot: "[1] acc*  [2] loss"
+        app.handle_mouse(mouse(click, runs.x + 2, runs.y + 2))
+            .unwrap();
+        wait_until(&mut app, |app| !app.is_loading_metrics());
+        draw(&app);
+        let selector = app.areas.get().metric_selector;
+        app.handle_mouse(mouse(click, selector.x + 12, selector.y))
+            .unwrap();
+        assert_eq!(app.metric_slot.selected_slot, 1);
+
+        // Dragging across the chart zooms into the range
+        draw(&app);
+        let chart = app.areas.get().chart;
+        let row = chart.y + chart.height / 2;
+        app.handle_mouse(mouse(click, chart.x + 20, row)).unwrap();
+        assert_eq!(app.focused, FocusedPanel::Chart);
+        app.handle_mouse(mouse(
+            MouseEventKind::Up(MouseButton::Left),
+            chart.x + 60,
+            row,
+        ))
+        .unwrap();
+        assert!(draw(&app).contains("zoom ×"));
+    }
+
+    #[test]
+    fn test_smoothing_keys() {
+        let mut app = App::with_backend(test_config(None), Box::new(make_backend()));
+        assert!(!app.smoothing.is_active());
+
+        app.handle_input(KeyCode::Char('m'), KeyModifiers::NONE)
+            .unwrap();
+        app.handle_input(KeyCode::Char('+'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.smoothing.label().as_deref(), Some("EMA 0.65"));
+        app.handle_input(KeyCode::Char('-'), KeyModifiers::NONE)
+            .unwrap();
+        app.handle_input(KeyCode::Char('m'), KeyModifiers::NONE)
+            .unwrap();
+        assert_eq!(app.smoothing.label().as_deref(), Some("median 0.60"));
+    }
+}
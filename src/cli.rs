@@ -3,8 +3,211 @@
 //! Provides CLI for launching the TUI dashboard:
 //! - `trackio-tui --project "my-project"`
 //! - `trackio-tui --interval 5`
+//! - `trackio-tui --watch`
+//! - `trackio-tui --source parquet --db-path ./exports`
+//! - `trackio-tui --image-protocol kitty`
+//! - `trackio-tui --resumed segments`
+//! - `trackio-tui --timezone utc`
+//! - `trackio-tui --format 'acc*=percent' --format 'lr=sci'`
 
-use clap::Parser;
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use clap::{Parser, ValueEnum};
+
+/// Format of the experiment data under `--db-path`
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DataSource {
+    /// trackio SQLite databases (one `<project>.db` per project)
+    #[default]
+    Trackio,
+    /// trackio Parquet exports (one `<project>.parquet` per project)
+    Parquet,
+    /// TensorBoard `tfevents` logs (one directory per run)
+    Tensorboard,
+    /// MLflow local file store (an `mlruns/` directory)
+    Mlflow,
+    /// CSV/JSONL metric logs (one directory per project, one file per run)
+    Logs,
+}
+
+/// How logged images are drawn in the terminal
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImageProtocol {
+    /// Pick the best protocol the terminal advertises
+    #[default]
+    Auto,
+    /// Colored half-block characters (needs a true-color terminal)
+    Halfblocks,
+    /// Monochrome braille dots (works everywhere)
+    Braille,
+    /// Kitty graphics protocol (kitty, WezTerm, Ghostty)
+    Kitty,
+    /// Sixel graphics (xterm -ti vt340, mlterm, foot, ...)
+    Sixel,
+}
+
+/// How metrics of resumed runs (steps logged again after a restart) are charted
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResumeMode {
+    /// Points written after a resume replace the earlier ones from its step on
+    #[default]
+    Latest,
+    /// Each stretch between resumes is drawn as its own line
+    Segments,
+    /// All points in step order, as logged
+    All,
+}
+
+impl ResumeMode {
+    /// The next mode, for cycling with a key
+    pub fn next(self) -> Self {
+        match self {
+            ResumeMode::Latest => ResumeMode::Segments,
+            ResumeMode::Segments => ResumeMode::All,
+            ResumeMode::All => ResumeMode::Latest,
+        }
+    }
+
+    /// Short name shown in the chart
+    pub fn label(self) -> &'static str {
+        match self {
+            ResumeMode::Latest => "latest",
+            ResumeMode::Segments => "segments",
+            ResumeMode::All => "all",
+        }
+    }
+}
+
+/// Time zone timestamps are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisplayTimezone {
+    /// The system's local time zone
+    #[default]
+    Local,
+    /// A fixed offset from UTC (UTC itself is an offset of zero)
+    Fixed(FixedOffset),
+}
+
+impl DisplayTimezone {
+    /// Format a timestamp in this time zone with a `strftime`-style format
+    pub fn format(self, timestamp: DateTime<Utc>, fmt: &str) -> String {
+        match self {
+            DisplayTimezone::Local => timestamp.with_timezone(&Local).format(fmt).to_string(),
+            DisplayTimezone::Fixed(offset) => {
+                timestamp.with_timezone(&offset).format(fmt).to_string()
+            }
+        }
+    }
+}
+
+/// Parse `local`, `utc` or an offset such as `+02:00`, `-0530` or `+9`
+fn parse_timezone(value: &str) -> Result<DisplayTimezone, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "local" => return Ok(DisplayTimezone::Local),
+        "utc" | "z" => return Ok(DisplayTimezone::Fixed(FixedOffset::east_opt(0).unwrap())),
+        _ => {}
+    }
+    let invalid =
+        || format!("invalid time zone '{value}' (expected local, utc or an offset like +02:00)");
+    let (sign, rest) = match value.as_bytes().first() {
+        Some(b'+') => (1, &value[1..]),
+        Some(b'-') => (-1, &value[1..]),
+        _ => return Err(invalid()),
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.as_str(), "0"),
+        4 => digits.split_at(2),
+        _ => return Err(invalid()),
+    };
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if minutes >= 60 {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+        .map(DisplayTimezone::Fixed)
+        .ok_or_else(invalid)
+}
+
+/// How chart labels and readout values of a metric are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValueFormat {
+    /// Plain decimals, SI suffixes (`3.2M`) or scientific notation, by magnitude
+    #[default]
+    Auto,
+    /// Fractions as percentages (`0.93` is `93%`)
+    Percent,
+    /// A fixed number of decimals
+    Fixed(usize),
+    /// Always scientific notation (`1.5e-5`)
+    Scientific,
+}
+
+/// Display format for metrics whose name matches a pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricFormat {
+    /// Metric name, where `*` matches any run of characters
+    pub pattern: String,
+    pub format: ValueFormat,
+}
+
+impl MetricFormat {
+    /// Whether the pattern matches the whole of `name`
+    pub fn matches(&self, name: &str) -> bool {
+        let mut parts = self.pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = name.strip_prefix(first) else {
+            return false;
+        };
+        let mut parts: Vec<&str> = parts.collect();
+        let Some(last) = parts.pop() else {
+            // No `*`: the name must equal the pattern
+            return rest.is_empty();
+        };
+        for part in parts {
+            match rest.find(part) {
+                Some(idx) => rest = &rest[idx + part.len()..],
+                None => return false,
+            }
+        }
+        rest.ends_with(last)
+    }
+
+    /// Format of the first of `formats` matching `name`, `Auto` if none does
+    pub fn find(formats: &[MetricFormat], name: &str) -> ValueFormat {
+        formats
+            .iter()
+            .find(|format| format.matches(name))
+            .map_or(ValueFormat::Auto, |format| format.format)
+    }
+}
+
+/// Parse `PATTERN=FORMAT`, where FORMAT is `auto`, `percent`, `sci` or `.N`
+/// for N decimals
+fn parse_metric_format(value: &str) -> Result<MetricFormat, String> {
+    let invalid = || {
+        format!("invalid format '{value}' (expected PATTERN=FORMAT with FORMAT auto, percent, sci or .N)")
+    };
+    let (pattern, format) = value.rsplit_once('=').ok_or_else(invalid)?;
+    if pattern.is_empty() {
+        return Err(invalid());
+    }
+    let format = match format.to_ascii_lowercase().as_str() {
+        "auto" => ValueFormat::Auto,
+        "percent" | "%" => ValueFormat::Percent,
+        "sci" | "scientific" => ValueFormat::Scientific,
+        digits => ValueFormat::Fixed(
+            digits
+                .strip_prefix('.')
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(invalid)?,
+        ),
+    };
+    Ok(MetricFormat {
+        pattern: pattern.to_string(),
+        format,
+    })
+}
 
 /// A Rust-based Terminal User Interface for visualizing trackio experiments.
 ///
@@ -21,10 +224,36 @@ pub struct Cli {
     #[arg(short, long, default_value = "2")]
     pub interval: u64,
 
-    /// Path to the trackio database directory
+    /// Path to the trackio database directory (repeatable to merge several roots)
     /// Defaults to ~/.cache/huggingface/trackio/
     #[arg(long)]
-    pub db_path: Option<String>,
+    pub db_path: Vec<String>,
+
+    /// Refresh when data files of the --source format change on disk instead of on a timer
+    /// (falls back to the --interval timer if watching is unavailable)
+    #[arg(short, long)]
+    pub watch: bool,
+
+    /// Format of the data found at --db-path
+    #[arg(long, value_enum, default_value_t = DataSource::Trackio)]
+    pub source: DataSource,
+
+    /// How to draw logged images
+    #[arg(long, value_enum, default_value_t = ImageProtocol::Auto)]
+    pub image_protocol: ImageProtocol,
+
+    /// How to chart runs resumed from a checkpoint (toggle with R)
+    #[arg(long, value_enum, default_value_t = ResumeMode::Latest)]
+    pub resumed: ResumeMode,
+
+    /// Time zone for timestamps: local, utc or an offset like +02:00
+    #[arg(long, default_value = "local", value_parser = parse_timezone)]
+    pub timezone: DisplayTimezone,
+
+    /// Display format for matching metrics, e.g. 'acc*=percent', 'lr=sci' or
+    /// 'loss=.3' (repeatable; the first matching pattern wins)
+    #[arg(long = "format", value_name = "PATTERN=FORMAT", value_parser = parse_metric_format)]
+    pub formats: Vec<MetricFormat>,
 }
 
 impl Cli {
@@ -39,35 +268,55 @@ impl Cli {
 pub struct AppConfig {
     pub project: Option<String>,
     pub refresh_interval_secs: u64,
-    pub db_path: std::path::PathBuf,
+    /// Data roots to browse; projects from all of them are merged
+    pub db_paths: Vec<std::path::PathBuf>,
+    pub watch: bool,
+    pub source: DataSource,
+    pub image_protocol: ImageProtocol,
+    pub resume_mode: ResumeMode,
+    pub timezone: DisplayTimezone,
+    /// Display formats by metric name pattern
+    pub formats: Vec<MetricFormat>,
 }
 
 impl AppConfig {
     /// Create AppConfig from CLI arguments
     pub fn from_cli(cli: &Cli) -> Self {
-        // Determine database path
-        let db_path = cli
-            .db_path
-            .as_ref()
-            .map(std::path::PathBuf::from)
-            .unwrap_or_else(|| {
-                // Check TRACKIO_DIR environment variable first
-                if let Ok(trackio_dir) = std::env::var("TRACKIO_DIR") {
-                    std::path::PathBuf::from(trackio_dir)
-                } else {
-                    // Default to ~/.cache/huggingface/trackio/
-                    dirs::home_dir()
-                        .unwrap_or_else(|| std::path::PathBuf::from("."))
-                        .join(".cache")
-                        .join("huggingface")
-                        .join("trackio")
-                }
-            });
+        // Determine database paths
+        let mut db_paths: Vec<std::path::PathBuf> = if !cli.db_path.is_empty() {
+            cli.db_path.iter().map(std::path::PathBuf::from).collect()
+        } else if let Some(trackio_dir) = std::env::var_os("TRACKIO_DIR") {
+            // TRACKIO_DIR may list several directories, separated like PATH
+            std::env::split_paths(&trackio_dir)
+                .filter(|p| !p.as_os_str().is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if db_paths.is_empty() {
+            // Default to ~/.cache/huggingface/trackio/
+            db_paths.push(
+                dirs::home_dir()
+                    .unwrap_or_else(|| std::path::PathBuf::from("."))
+                    .join(".cache")
+                    .join("huggingface")
+                    .join("trackio"),
+            );
+        }
+        // Listing a root twice would show each of its projects twice
+        let mut seen = std::collections::HashSet::new();
+        db_paths.retain(|p| seen.insert(p.clone()));
 
         AppConfig {
             project: cli.project.clone(),
             refresh_interval_secs: cli.interval,
-            db_path,
+            db_paths,
+            watch: cli.watch,
+            source: cli.source,
+            image_protocol: cli.image_protocol,
+            resume_mode: cli.resumed,
+            timezone: cli.timezone,
+            formats: cli.formats.clone(),
         }
     }
 }
@@ -81,9 +330,79 @@ mod tests {
         let cli = Cli {
             project: None,
             interval: 2,
-            db_path: None,
+            db_path: Vec::new(),
+            watch: false,
+            source: DataSource::default(),
+            image_protocol: ImageProtocol::default(),
+            resumed: ResumeMode::default(),
+            timezone: DisplayTimezone::default(),
+            formats: Vec::new(),
         };
         let config = AppConfig::from_cli(&cli);
         assert_eq!(config.refresh_interval_secs, 2);
+        assert!(!config.watch);
+        assert_eq!(config.source, DataSource::Trackio);
+    }
+
+    #[test]
+    fn test_source_flag() {
+        let cli = Cli::parse_from(["trackio-tui", "--source", "parquet"]);
+        assert_eq!(AppConfig::from_cli(&cli).source, DataSource::Parquet);
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        let offset = |secs| DisplayTimezone::Fixed(FixedOffset::east_opt(secs).unwrap());
+        assert_eq!(parse_timezone("local"), Ok(DisplayTimezone::Local));
+        assert_eq!(parse_timezone("UTC"), Ok(offset(0)));
+        assert_eq!(parse_timezone("+02:00"), Ok(offset(7200)));
+        assert_eq!(parse_timezone("-0530"), Ok(offset(-19800)));
+        assert_eq!(parse_timezone("+9"), Ok(offset(32400)));
+        assert!(parse_timezone("Europe/Paris").is_err());
+        assert!(parse_timezone("+02:75").is_err());
+    }
+
+    #[test]
+    fn test_metric_formats() {
+        let cli = Cli::parse_from([
+            "trackio-tui",
+            "--format",
+            "train/acc*=percent",
+            "--format",
+            "*loss=.3",
+            "--format",
+            "lr=sci",
+        ]);
+        let formats = AppConfig::from_cli(&cli).formats;
+        let find = |name| MetricFormat::find(&formats, name);
+        assert_eq!(find("train/accuracy"), ValueFormat::Percent);
+        assert_eq!(find("val/accuracy"), ValueFormat::Auto);
+        assert_eq!(find("train/loss"), ValueFormat::Fixed(3));
+        assert_eq!(find("loss/train"), ValueFormat::Auto);
+        assert_eq!(find("lr"), ValueFormat::Scientific);
+        assert_eq!(find("lr_decay"), ValueFormat::Auto);
+        assert!(parse_metric_format("loss").is_err());
+        assert!(parse_metric_format("loss=3").is_err());
+    }
+
+    #[test]
+    fn test_repeated_db_path() {
+        let cli = Cli::parse_from([
+            "trackio-tui",
+            "--db-path",
+            "/local/trackio",
+            "--db-path",
+            "/nfs/trackio",
+            "--db-path",
+            "/local/trackio",
+        ]);
+        let config = AppConfig::from_cli(&cli);
+        assert_eq!(
+            config.db_paths,
+            vec![
+                std::path::PathBuf::from("/local/trackio"),
+                std::path::PathBuf::from("/nfs/trackio")
+            ]
+        );
     }
 }
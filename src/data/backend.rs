@@ -0,0 +1,48 @@
+//! Storage backend abstraction.
+//!
+//! The app reads experiment data through `StorageBackend`, so sources other
+//! than trackio's SQLite databases can be plugged in.
+
+use anyhow::Result;
+
+use super::models::{Metric, MetricsUpdate, Project, RichValues, RichValuesUpdate, Run};
+
+/// A source of experiment data.
+///
+/// Implementations are moved onto the background loader thread, hence `Send`.
+pub trait StorageBackend: Send {
+    /// List all available projects
+    fn list_projects(&self) -> Result<Vec<Project>>;
+
+    /// List all runs of a project, including their config
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>>;
+
+    /// Load all metrics for a run, sorted by name
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>>;
+
+    /// Load metrics logged since the last load of a run.
+    /// Backends without incremental support reload everything.
+    fn get_new_metrics(&self, project: &str, run_id: &str) -> Result<MetricsUpdate> {
+        self.get_all_metrics(project, run_id)
+            .map(MetricsUpdate::Reload)
+    }
+
+    /// Load a run's system (hardware) metrics kept apart from its training metrics,
+    /// named with the `system/` prefix. Backends without such data return none.
+    fn get_system_metrics(&self, _project: &str, _run_id: &str) -> Result<Vec<Metric>> {
+        Ok(Vec::new())
+    }
+
+    /// Load a run's non-scalar values (e.g. histograms).
+    /// Backends that only store scalars return none.
+    fn get_rich_values(&self, _project: &str, _run_id: &str) -> Result<RichValues> {
+        Ok(RichValues::default())
+    }
+
+    /// Load non-scalar values logged since the last load of a run.
+    /// Backends without incremental support reload everything.
+    fn get_new_rich_values(&self, project: &str, run_id: &str) -> Result<RichValuesUpdate> {
+        self.get_rich_values(project, run_id)
+            .map(RichValuesUpdate::Reload)
+    }
+}
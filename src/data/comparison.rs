@@ -5,7 +5,7 @@
 
 use std::collections::{HashMap, HashSet};
 
-use super::Metric;
+use super::{Metric, MetricsUpdate};
 
 /// Manages run comparison state and cached metrics
 #[derive(Debug, Default)]
@@ -56,8 +56,13 @@ impl ComparisonState {
         self.metrics_cache.insert(run_id.to_string(), metrics);
     }
 
+    /// Apply an incremental update to a run's cached metrics
+    pub fn apply_update(&mut self, run_id: &str, update: MetricsUpdate) {
+        let metrics = self.metrics_cache.entry(run_id.to_string()).or_default();
+        update.apply(metrics);
+    }
+
     /// Get cached metrics for a specific run
-    #[allow(dead_code)] // Used in tests
     pub fn get_cached_metrics(&self, run_id: &str) -> Option<&Vec<Metric>> {
         self.metrics_cache.get(run_id)
     }
@@ -100,9 +105,9 @@ mod tests {
     use crate::data::MetricPoint;
 
     fn make_metric(name: &str, values: &[f64]) -> Metric {
-        Metric {
-            name: name.to_string(),
-            points: values
+        Metric::with_points(
+            name.to_string(),
+            values
                 .iter()
                 .enumerate()
                 .map(|(i, &v)| MetricPoint {
@@ -111,7 +116,7 @@ mod tests {
                     timestamp: None,
                 })
                 .collect(),
-        }
+        )
     }
 
     #[test]
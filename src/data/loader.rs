@@ -0,0 +1,280 @@
+//! Background data loading.
+//!
+//! A worker thread owns the storage backend and serves load requests sent over a
+//! channel, so the UI thread never blocks on SQLite. Responses carry the
+//! project/run they were requested for, letting the app drop stale results
+//! after the selection has moved on.
+
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use anyhow::Result;
+
+use super::{Metric, MetricsUpdate, Project, RichValuesUpdate, Run, StorageBackend};
+
+/// A request for the background loader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadRequest {
+    /// List all projects
+    Projects,
+    /// List the runs of a project
+    Runs { project: String },
+    /// Load metrics for a run
+    Metrics(MetricsRequest),
+    /// Load system metrics for the selected run
+    SystemMetrics { project: String, run_id: String },
+    /// Load non-scalar values (histograms, ...) for the selected run,
+    /// only those logged since its last load if `incremental`
+    RichValues {
+        project: String,
+        run_id: String,
+        incremental: bool,
+    },
+}
+
+/// A request for a run's metrics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricsRequest {
+    pub project: String,
+    pub run_id: String,
+    /// Only fetch points logged since the last load of this run
+    pub incremental: bool,
+    /// Requested for the selected run (as opposed to a comparison run).
+    /// Queued requests for the selected run are superseded by newer ones.
+    pub selected: bool,
+}
+
+/// A completed load, tagged with what was requested
+pub enum LoadResponse {
+    Projects(Result<Vec<Project>>),
+    Runs {
+        project: String,
+        result: Result<Vec<Run>>,
+    },
+    Metrics {
+        project: String,
+        run_id: String,
+        result: Result<MetricsUpdate>,
+    },
+    SystemMetrics {
+        project: String,
+        run_id: String,
+        result: Result<Vec<Metric>>,
+    },
+    RichValues {
+        project: String,
+        run_id: String,
+        result: Result<RichValuesUpdate>,
+    },
+}
+
+/// Handle to the background loader thread.
+/// The thread exits once this handle is dropped.
+pub struct Loader {
+    requests: Sender<LoadRequest>,
+    responses: Receiver<LoadResponse>,
+}
+
+impl Loader {
+    /// Spawn the loader thread, moving `storage` onto it
+    pub fn spawn(storage: Box<dyn StorageBackend>) -> Self {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (response_tx, response_rx) = mpsc::channel();
+
+        thread::spawn(move || serve(storage, request_rx, response_tx));
+
+        Loader {
+            requests: request_tx,
+            responses: response_rx,
+        }
+    }
+
+    /// Queue a load request
+    pub fn request(&self, request: LoadRequest) {
+        // The worker only stops when this handle is dropped, so sending cannot fail
+        let _ = self.requests.send(request);
+    }
+
+    /// Take the next completed load, if any
+    pub fn try_recv(&self) -> Option<LoadResponse> {
+        self.responses.try_recv().ok()
+    }
+}
+
+/// Worker loop: serve requests until the app side hangs up
+fn serve(
+    storage: Box<dyn StorageBackend>,
+    requests: Receiver<LoadRequest>,
+    responses: Sender<LoadResponse>,
+) {
+    while let Ok(first) = requests.recv() {
+        // Batch everything queued so superseded requests can be skipped
+        let mut batch = vec![first];
+        loop {
+            match requests.try_recv() {
+                Ok(request) => batch.push(request),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return,
+            }
+        }
+
+        for request in coalesce(batch) {
+            let response = match request {
+                LoadRequest::Projects => LoadResponse::Projects(storage.list_projects()),
+                LoadRequest::Runs { project } => {
+                    let result = storage.list_runs(&project);
+                    LoadResponse::Runs { project, result }
+                }
+                LoadRequest::Metrics(request) => {
+                    let result = if request.incremental {
+                        storage.get_new_metrics(&request.project, &request.run_id)
+                    } else {
+                        storage
+                            .get_all_metrics(&request.project, &request.run_id)
+                            .map(MetricsUpdate::Reload)
+                    };
+                    LoadResponse::Metrics {
+                        project: request.project,
+                        run_id: request.run_id,
+                        result,
+                    }
+                }
+                LoadRequest::SystemMetrics { project, run_id } => {
+                    let result = storage.get_system_metrics(&project, &run_id);
+                    LoadResponse::SystemMetrics {
+                        project,
+                        run_id,
+                        result,
+                    }
+                }
+                LoadRequest::RichValues {
+                    project,
+                    run_id,
+                    incremental,
+                } => {
+                    let result = if incremental {
+                        storage.get_new_rich_values(&project, &run_id)
+                    } else {
+                        storage
+                            .get_rich_values(&project, &run_id)
+                            .map(RichValuesUpdate::Reload)
+                    };
+                    LoadResponse::RichValues {
+                        project,
+                        run_id,
+                        result,
+                    }
+                }
+            };
+            if responses.send(response).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Drop queued requests made obsolete by a later request in the same batch:
+/// repeated project/run listings and selected-run loads the user has scrolled past
+/// (system metrics and rich values are only ever loaded for the selected run).
+fn coalesce(batch: Vec<LoadRequest>) -> Vec<LoadRequest> {
+    let is_superseded = |idx: usize, request: &LoadRequest| {
+        batch[idx + 1..].iter().any(|later| match (request, later) {
+            (LoadRequest::Projects, LoadRequest::Projects) => true,
+            (LoadRequest::Runs { .. }, LoadRequest::Runs { .. }) => true,
+            (LoadRequest::SystemMetrics { .. }, LoadRequest::SystemMetrics { .. }) => true,
+            (LoadRequest::RichValues { .. }, LoadRequest::RichValues { .. }) => true,
+            (LoadRequest::Metrics(a), LoadRequest::Metrics(b)) => a.selected && b.selected,
+            _ => false,
+        })
+    };
+
+    batch
+        .iter()
+        .enumerate()
+        .filter(|(idx, request)| !is_superseded(*idx, request))
+        .map(|(_, request)| request.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MemoryBackend;
+    use std::time::{Duration, Instant};
+
+    fn metrics_request(run_id: &str, selected: bool) -> LoadRequest {
+        LoadRequest::Metrics(MetricsRequest {
+            project: "proj".to_string(),
+            run_id: run_id.to_string(),
+            incremental: false,
+            selected,
+        })
+    }
+
+    #[test]
+    fn test_coalesce_skips_superseded_selected_loads() {
+        let batch = vec![
+            metrics_request("run-0", true),
+            metrics_request("run-1", false),
+            metrics_request("run-2", true),
+            metrics_request("run-3", true),
+        ];
+
+        let kept = coalesce(batch);
+
+        // Comparison loads are always kept; only the last selected load survives
+        assert_eq!(
+            kept,
+            vec![
+                metrics_request("run-1", false),
+                metrics_request("run-3", true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_keeps_last_listing() {
+        let batch = vec![
+            LoadRequest::Projects,
+            LoadRequest::Runs {
+                project: "a".to_string(),
+            },
+            LoadRequest::Projects,
+            LoadRequest::Runs {
+                project: "b".to_string(),
+            },
+        ];
+
+        let kept = coalesce(batch);
+
+        assert_eq!(
+            kept,
+            vec![
+                LoadRequest::Projects,
+                LoadRequest::Runs {
+                    project: "b".to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_loader_serves_requests() {
+        let loader = Loader::spawn(Box::new(MemoryBackend::new()));
+
+        loader.request(LoadRequest::Projects);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            if let Some(response) = loader.try_recv() {
+                match response {
+                    LoadResponse::Projects(result) => assert!(result.unwrap().is_empty()),
+                    _ => panic!("unexpected response"),
+                }
+                break;
+            }
+            assert!(Instant::now() < deadline, "loader did not respond");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Storage backend for plain CSV/JSONL metric logs (read-only).
+//!
+//! Each directory of log files is a project and each file a run, named after
+//! the file stem:
+//! - `<run>.csv`: `step,metric,value` rows, with an optional header naming
+//!   the columns (`step`, `metric`, `value` and optionally `timestamp`)
+//! - `<run>.jsonl`: one JSON object per line; numeric fields are metrics,
+//!   `step` and `timestamp` are taken from fields of those names
+//! - `<run>.json`: optional sidecar holding the run's config as a JSON object
+//!   (ignored if it doesn't parse)
+//!
+//! A run logged as both `<run>.csv` and `<run>.jsonl` lists as two runs named
+//! after the full file names.
+//!
+//! Subdirectories of the root are projects; log files directly in the root
+//! form a project named after the root directory.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use super::backend::StorageBackend;
+use super::models::{Metric, MetricPoint, Project, Run};
+use super::storage::{metric_value, parse_config_json, parse_metrics_json, parse_timestamp};
+
+/// Storage backend reading directories of CSV/JSONL metric logs
+pub struct LogFileBackend {
+    root: PathBuf,
+}
+
+/// Format of a run's log file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    Csv,
+    Jsonl,
+}
+
+impl LogFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(LogFormat::Csv),
+            "jsonl" => Some(LogFormat::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// A run's log file
+struct LogFile {
+    run: String,
+    path: PathBuf,
+    format: LogFormat,
+}
+
+impl LogFileBackend {
+    /// Create a backend reading log directories under `root`
+    pub fn new(root: PathBuf) -> Self {
+        LogFileBackend { root }
+    }
+
+    /// Project directories keyed by project name
+    fn project_dirs(&self) -> Result<BTreeMap<String, PathBuf>> {
+        let mut dirs = BTreeMap::new();
+        if !self.root.exists() {
+            return Ok(dirs);
+        }
+
+        let entries = std::fs::read_dir(&self.root)
+            .with_context(|| format!("Failed to read directory: {:?}", self.root))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().is_dir() && !name.starts_with('.') {
+                dirs.insert(name, entry.path());
+            }
+        }
+
+        if !log_files(&self.root)?.is_empty() {
+            let root_name = self
+                .root
+                .canonicalize()
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .unwrap_or_else(|| "logs".to_string());
+            dirs.entry(root_name).or_insert_with(|| self.root.clone());
+        }
+        Ok(dirs)
+    }
+
+    fn project_dir(&self, project: &str) -> Result<PathBuf> {
+        self.project_dirs()?
+            .remove(project)
+            .with_context(|| format!("Project not found: {project}"))
+    }
+}
+
+impl StorageBackend for LogFileBackend {
+    fn list_projects(&self) -> Result<Vec<Project>> {
+        let mut projects = Vec::new();
+        for (name, dir) in self.project_dirs()? {
+            let files = log_files(&dir)?;
+            if files.is_empty() {
+                continue;
+            }
+            projects.push(Project::new(
+                name,
+                files.len(),
+                files.iter().filter_map(|f| modified_at(&f.path)).max(),
+            ));
+        }
+
+        // Sort by last updated (most recent first)
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_updated));
+        Ok(projects)
+    }
+
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
+        let mut runs: Vec<Run> = log_files(&self.project_dir(project)?)?
+            .into_iter()
+            .map(|file| {
+                // A broken sidecar shouldn't hide the run's metrics
+                let config = std::fs::read_to_string(file.path.with_extension("json"))
+                    .ok()
+                    .and_then(|json| parse_config_json(&json).ok())
+                    .unwrap_or_default();
+                let created_at = std::fs::metadata(&file.path)
+                    .and_then(|m| m.created())
+                    .ok()
+                    .map(DateTime::<Utc>::from)
+                    .or_else(|| modified_at(&file.path));
+                Run::new(file.run, project.to_string(), created_at, config)
+            })
+            .collect();
+        runs.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+        Ok(runs)
+    }
+
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        let file = log_files(&self.project_dir(project)?)?
+            .into_iter()
+            .find(|f| f.run == run_id)
+            .with_context(|| format!("Run not found: {run_id}"))?;
+        let contents = std::fs::read_to_string(&file.path)
+            .with_context(|| format!("Failed to read file: {:?}", file.path))?;
+
+        let points = match file.format {
+            LogFormat::Csv => parse_csv(&contents),
+            LogFormat::Jsonl => parse_jsonl(&contents),
+        };
+
+        let mut metrics_map: BTreeMap<String, Metric> = BTreeMap::new();
+        for (name, point) in points {
+            metrics_map
+                .entry(name.clone())
+                .or_insert_with(|| Metric::new(name))
+                .points_mut()
+                .push(point);
+        }
+
+        let mut metrics: Vec<Metric> = metrics_map.into_values().collect();
+        for metric in &mut metrics {
+            // Stable sort keeps the logged order of points sharing a step
+            metric.points_mut().sort_by_key(|p| p.step);
+        }
+        Ok(metrics)
+    }
+}
+
+/// Log files directly inside `dir`, one per run. Runs are named after the
+/// file stem, or the full file name when several files share the stem.
+fn log_files(dir: &Path) -> Result<Vec<LogFile>> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read directory: {dir:?}"))?;
+    let mut files: Vec<LogFile> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter_map(|path| {
+            let format = LogFormat::from_path(&path)?;
+            let run = path.file_stem()?.to_string_lossy().into_owned();
+            Some(LogFile { run, path, format })
+        })
+        .collect();
+
+    // Run ids must be unique within a project
+    let mut stem_counts: HashMap<String, usize> = HashMap::new();
+    for file in &files {
+        *stem_counts.entry(file.run.clone()).or_default() += 1;
+    }
+    for file in &mut files {
+        if stem_counts[&file.run] > 1 {
+            if let Some(name) = file.path.file_name() {
+                file.run = name.to_string_lossy().into_owned();
+            }
+        }
+    }
+    files.sort_by(|a, b| a.run.cmp(&b.run));
+    Ok(files)
+}
+
+fn modified_at(path: &Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Utc>::from)
+}
+
+/// Column positions of a long-format CSV log
+struct CsvColumns {
+    step: usize,
+    metric: usize,
+    value: usize,
+    timestamp: Option<usize>,
+}
+
+/// Parse `step,metric,value` rows into (metric name, point) pairs.
+/// Malformed rows (e.g. a line still being written) are skipped.
+fn parse_csv(contents: &str) -> Vec<(String, MetricPoint)> {
+    let mut lines = contents.lines().filter(|l| !l.trim().is_empty()).peekable();
+
+    let mut columns = CsvColumns {
+        step: 0,
+        metric: 1,
+        value: 2,
+        timestamp: None,
+    };
+    if let Some(first) = lines.peek() {
+        let header = split_csv_line(first);
+        let position = |name: &str| header.iter().position(|h| h.eq_ignore_ascii_case(name));
+        if let (Some(step), Some(metric), Some(value)) = (
+            position("step"),
+            position("metric").or_else(|| position("name")),
+            position("value"),
+        ) {
+            columns = CsvColumns {
+                step,
+                metric,
+                value,
+                timestamp: position("timestamp"),
+            };
+            lines.next();
+        }
+    }
+
+    lines
+        .filter_map(|line| {
+            let fields = split_csv_line(line);
+            let step = fields.get(columns.step)?.parse().ok()?;
+            let value = fields.get(columns.value)?.parse().ok()?;
+            let name = fields.get(columns.metric)?.to_string();
+            let timestamp = columns
+                .timestamp
+                .and_then(|i| fields.get(i))
+                .and_then(|t| parse_timestamp(Some(t.to_string())));
+            Some((
+                name,
+                MetricPoint {
+                    step,
+                    value,
+                    timestamp,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Split a CSV line on commas, honouring double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Parse one-object-per-line logs into (metric name, point) pairs.
+/// Lines without a `step` field use their line index as the step.
+fn parse_jsonl(contents: &str) -> Vec<(String, MetricPoint)> {
+    let mut points = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let Some(map) = parse_metrics_json(line) else {
+            continue;
+        };
+
+        let step = map
+            .get("step")
+            .and_then(|s| s.as_i64())
+            .unwrap_or(idx as i64);
+        let timestamp = map.get("timestamp").and_then(|t| match t {
+            serde_json::Value::String(s) => parse_timestamp(Some(s.clone())),
+            serde_json::Value::Number(n) => n.as_f64().and_then(|secs| {
+                DateTime::from_timestamp(secs.trunc() as i64, (secs.fract() * 1e9) as u32)
+            }),
+            _ => None,
+        });
+
+        for (name, value) in map {
+            if name == "step" || name == "timestamp" {
+                continue;
+            }
+            if let Some(value) = metric_value(&value) {
+                points.push((
+                    name,
+                    MetricPoint {
+                        step,
+                        value,
+                        timestamp,
+                    },
+                ));
+            }
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_with_and_without_header() {
+        let points = parse_csv("1,loss,0.5\n0,loss,1.0\n0,acc,0.1\n");
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].0, "loss");
+        assert_eq!(points[0].1.step, 1);
+
+        let points = parse_csv(
+            "timestamp,metric,step,value\n2024-01-01T00:00:00,\"train,loss\",3,2.5\n0,loss,oops\n",
+        );
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].0, "train,loss");
+        assert_eq!(points[0].1.step, 3);
+        assert_eq!(points[0].1.value, 2.5);
+        assert!(points[0].1.timestamp.is_some());
+    }
+
+    #[test]
+    fn test_parse_jsonl() {
+        let points = parse_jsonl(
+            "{\"step\": 5, \"loss\": 0.5, \"note\": \"hi\", \"timestamp\": 1700000000}\n{\"loss\": 0.25}\n{\"loss\": \n",
+        );
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].1.step, 5);
+        assert_eq!(points[0].1.timestamp.unwrap().timestamp(), 1_700_000_000);
+        // No step field: the line index is used
+        assert_eq!(points[1].1.step, 1);
+    }
+
+    #[test]
+    fn test_log_file_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("quick");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(
+            project.join("baseline.csv"),
+            "step,metric,value\n0,loss,1.0\n1,loss,0.5\n",
+        )
+        .unwrap();
+        std::fs::write(project.join("baseline.json"), "{\"lr\": 0.01}").unwrap();
+        std::fs::write(
+            project.join("tuned.jsonl"),
+            "{\"step\": 0, \"loss\": 0.8, \"acc\": 0.2}\n",
+        )
+        .unwrap();
+        std::fs::write(project.join("notes.txt"), "ignored").unwrap();
+
+        let backend = LogFileBackend::new(dir.path().to_path_buf());
+
+        let projects = backend.list_projects().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name, "quick");
+        assert_eq!(projects[0].run_count, 2);
+
+        let runs = backend.list_runs("quick").unwrap();
+        let baseline = runs.iter().find(|r| r.id == "baseline").unwrap();
+        assert_eq!(baseline.config.len(), 1);
+        assert_eq!(baseline.config[0].key, "lr");
+        assert!(runs
+            .iter()
+            .find(|r| r.id == "tuned")
+            .unwrap()
+            .config
+            .is_empty());
+
+        let metrics = backend.get_all_metrics("quick", "baseline").unwrap();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].points().len(), 2);
+
+        let metrics = backend.get_all_metrics("quick", "tuned").unwrap();
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["acc", "loss"]);
+
+        assert!(backend.get_all_metrics("quick", "notes").is_err());
+    }
+
+    #[test]
+    fn test_shared_stem_and_broken_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("run.csv"), "0,loss,1.0\n").unwrap();
+        std::fs::write(dir.path().join("run.jsonl"), "{\"acc\": 0.5}\n").unwrap();
+        std::fs::write(dir.path().join("run.json"), "{not json").unwrap();
+
+        let backend = LogFileBackend::new(dir.path().to_path_buf());
+        let project = backend.list_projects().unwrap().remove(0).name;
+        let runs = backend.list_runs(&project).unwrap();
+        let mut ids: Vec<&str> = runs.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["run.csv", "run.jsonl"]);
+        assert!(runs.iter().all(|r| r.config.is_empty()));
+
+        let metrics = backend.get_all_metrics(&project, "run.jsonl").unwrap();
+        assert_eq!(metrics[0].name, "acc");
+    }
+}
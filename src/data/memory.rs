@@ -0,0 +1,161 @@
+//! In-memory storage backend, used to drive the app in tests.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use super::backend::StorageBackend;
+use super::models::{Metric, Project, RichValues, Run};
+
+/// A run and its metrics held in memory
+#[derive(Debug, Clone)]
+struct MemoryRun {
+    run: Run,
+    metrics: Vec<Metric>,
+    rich_values: RichValues,
+}
+
+/// Storage backend holding all data in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBackend {
+    /// Runs keyed by project name, in insertion order
+    projects: BTreeMap<String, Vec<MemoryRun>>,
+}
+
+impl MemoryBackend {
+    /// Create an empty backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a run with its metrics, creating the project if needed
+    pub fn add_run(&mut self, run: Run, mut metrics: Vec<Metric>) -> &mut Self {
+        metrics.sort_by(|a, b| a.name.cmp(&b.name));
+        self.projects
+            .entry(run.project.clone())
+            .or_default()
+            .push(MemoryRun {
+                run,
+                metrics,
+                rich_values: RichValues::default(),
+            });
+        self
+    }
+
+    /// Attach non-scalar values to a previously added run
+    pub fn set_rich_values(&mut self, project: &str, run_id: &str, rich_values: RichValues) {
+        if let Some(run) = self
+            .projects
+            .get_mut(project)
+            .and_then(|runs| runs.iter_mut().find(|r| r.run.id == run_id))
+        {
+            run.rich_values = rich_values;
+        }
+    }
+
+    fn find_run(&self, project: &str, run_id: &str) -> Result<&MemoryRun> {
+        self.projects
+            .get(project)
+            .with_context(|| format!("Project not found: {project}"))?
+            .iter()
+            .find(|r| r.run.id == run_id)
+            .with_context(|| format!("Run not found: {run_id}"))
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn list_projects(&self) -> Result<Vec<Project>> {
+        Ok(self
+            .projects
+            .iter()
+            .map(|(name, runs)| {
+                Project::new(
+                    name.clone(),
+                    runs.len(),
+                    runs.iter().filter_map(|r| r.run.created_at).max(),
+                )
+            })
+            .collect())
+    }
+
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
+        Ok(self
+            .projects
+            .get(project)
+            .map(|runs| {
+                runs.iter()
+                    .map(|r| Run {
+                        diverged: r
+                            .metrics
+                            .iter()
+                            .any(|m| !m.is_system() && m.latest_is_non_finite()),
+                        ..r.run.clone()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        Ok(self.find_run(project, run_id)?.metrics.clone())
+    }
+
+    fn get_rich_values(&self, project: &str, run_id: &str) -> Result<RichValues> {
+        Ok(self.find_run(project, run_id)?.rich_values.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{MetricPoint, MetricsUpdate};
+
+    fn make_run(project: &str, id: &str) -> Run {
+        Run::new(id.to_string(), project.to_string(), None, Vec::new())
+    }
+
+    fn make_metric(name: &str, values: &[f64]) -> Metric {
+        Metric::with_points(
+            name.to_string(),
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| MetricPoint {
+                    step: i as i64,
+                    value: v,
+                    timestamp: None,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_memory_backend_lists_and_loads() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_run(
+                make_run("proj", "run-0"),
+                vec![make_metric("loss", &[1.0, 0.5]), make_metric("acc", &[0.1])],
+            )
+            .add_run(make_run("proj", "run-1"), Vec::new());
+
+        let projects = backend.list_projects().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].run_count, 2);
+
+        let runs = backend.list_runs("proj").unwrap();
+        assert_eq!(runs.len(), 2);
+
+        // Metrics come back sorted by name
+        let metrics = backend.get_all_metrics("proj", "run-0").unwrap();
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["acc", "loss"]);
+
+        // Default incremental load falls back to a full reload
+        assert!(matches!(
+            backend.get_new_metrics("proj", "run-0").unwrap(),
+            MetricsUpdate::Reload(_)
+        ));
+        assert!(backend.get_all_metrics("proj", "missing").is_err());
+    }
+}
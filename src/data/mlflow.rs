@@ -0,0 +1,390 @@
+//! MLflow file-store backend (read-only).
+//!
+//! Reads the local `mlruns/` layout written by MLflow's file store:
+//! - `<experiment_id>/meta.yaml`: experiment name, mapped to a project
+//! - `<experiment_id>/<run_id>/meta.yaml`: run name and start time
+//! - `<run_id>/params/<key>`: one param value per file, mapped to config
+//! - `<run_id>/metrics/<key>`: `<timestamp_ms> <value> <step>` lines
+//!
+//! Metric and param keys containing `/` are stored in nested directories.
+//! Deleted experiments and runs (`lifecycle_stage: deleted`) are skipped.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use super::backend::StorageBackend;
+use super::models::{Config, ConfigValue, Metric, MetricPoint, Project, Run};
+
+/// File holding experiment and run metadata
+const META_FILE: &str = "meta.yaml";
+
+/// Tag MLflow uses for run names in versions that don't write `run_name` to meta.yaml
+const RUN_NAME_TAG: &str = "mlflow.runName";
+
+/// Storage backend reading an MLflow `mlruns/` directory
+pub struct MlflowBackend {
+    root: PathBuf,
+}
+
+/// An experiment directory and its name
+struct Experiment {
+    name: String,
+    path: PathBuf,
+}
+
+impl MlflowBackend {
+    /// Create a backend reading the file store at `root`
+    pub fn new(root: PathBuf) -> Self {
+        MlflowBackend { root }
+    }
+
+    /// List active experiments
+    fn experiments(&self) -> Result<Vec<Experiment>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = std::fs::read_dir(&self.root)
+            .with_context(|| format!("Failed to read directory: {:?}", self.root))?;
+        let mut experiments = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            // `.trash` holds deleted experiments; `models` is the model registry
+            if !path.is_dir() || entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Some(meta) = read_meta(&path) else {
+                continue;
+            };
+            if is_deleted(&meta) {
+                continue;
+            }
+            if let Some(name) = meta.get("name") {
+                experiments.push(Experiment {
+                    name: name.clone(),
+                    path,
+                });
+            }
+        }
+        Ok(experiments)
+    }
+
+    fn find_experiment(&self, project: &str) -> Result<Experiment> {
+        self.experiments()?
+            .into_iter()
+            .find(|e| e.name == project)
+            .with_context(|| format!("Experiment not found: {project}"))
+    }
+
+    fn run_dir(&self, project: &str, run_id: &str) -> Result<PathBuf> {
+        let dir = self.find_experiment(project)?.path.join(run_id);
+        if read_meta(&dir).is_none_or(|meta| is_deleted(&meta)) {
+            anyhow::bail!("Run not found: {run_id}");
+        }
+        Ok(dir)
+    }
+}
+
+impl StorageBackend for MlflowBackend {
+    fn list_projects(&self) -> Result<Vec<Project>> {
+        let mut projects: Vec<Project> = self
+            .experiments()?
+            .into_iter()
+            .map(|experiment| {
+                let runs = read_runs(&experiment.path, &experiment.name)?;
+                Ok(Project::new(
+                    experiment.name,
+                    runs.len(),
+                    runs.iter().filter_map(|r| r.created_at).max(),
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        // Sort by last updated (most recent first)
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_updated));
+        Ok(projects)
+    }
+
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
+        let experiment = self.find_experiment(project)?;
+        let mut runs = read_runs(&experiment.path, project)?;
+        runs.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+        Ok(runs)
+    }
+
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        let metrics_dir = self.run_dir(project, run_id)?.join("metrics");
+        let mut metrics: Vec<Metric> = read_key_files(&metrics_dir)?
+            .into_iter()
+            .map(|(name, path)| {
+                let contents = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read file: {path:?}"))?;
+                let mut points: Vec<MetricPoint> =
+                    contents.lines().filter_map(parse_metric_line).collect();
+                points.sort_by_key(|p| (p.step, p.timestamp));
+                Ok(Metric::with_points(name, points))
+            })
+            .collect::<Result<_>>()?;
+        metrics.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(metrics)
+    }
+}
+
+/// Read the active runs of an experiment directory
+fn read_runs(experiment_dir: &Path, project: &str) -> Result<Vec<Run>> {
+    let entries = std::fs::read_dir(experiment_dir)
+        .with_context(|| format!("Failed to read directory: {experiment_dir:?}"))?;
+    let mut runs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(meta) = read_meta(&path) else {
+            continue;
+        };
+        if is_deleted(&meta) {
+            continue;
+        }
+
+        let id = entry.file_name().to_string_lossy().into_owned();
+        let created_at = meta
+            .get("start_time")
+            .and_then(|t| t.parse::<i64>().ok())
+            .and_then(DateTime::<Utc>::from_timestamp_millis);
+        let name = meta
+            .get("run_name")
+            .filter(|n| !n.is_empty())
+            .cloned()
+            .or_else(|| {
+                std::fs::read_to_string(path.join("tags").join(RUN_NAME_TAG))
+                    .ok()
+                    .map(|n| n.trim().to_string())
+            });
+
+        let config = read_params(&path.join("params"))?;
+        let run = Run::new(id, project.to_string(), created_at, config);
+        runs.push(match name {
+            Some(name) => run.with_display_name(&name),
+            None => run,
+        });
+    }
+    Ok(runs)
+}
+
+/// Read a run's params as config, sorted by key
+fn read_params(params_dir: &Path) -> Result<Vec<Config>> {
+    let mut configs: Vec<Config> = read_key_files(params_dir)?
+        .into_iter()
+        .map(|(key, path)| {
+            let value = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read file: {path:?}"))?;
+            Ok(Config {
+                key,
+                value: parse_param_value(value.trim_end_matches('\n')),
+            })
+        })
+        .collect::<Result<_>>()?;
+    configs.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(configs)
+}
+
+/// Collect `(key, path)` for every file under `dir`, where the key is the
+/// `/`-joined path relative to `dir`. A missing directory has no keys.
+fn read_key_files(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    fn walk(dir: &Path, prefix: &str, out: &mut Vec<(String, PathBuf)>) -> Result<()> {
+        let entries =
+            std::fs::read_dir(dir).with_context(|| format!("Failed to read directory: {dir:?}"))?;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let key = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            };
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, &key, out)?;
+            } else {
+                out.push((key, path));
+            }
+        }
+        Ok(())
+    }
+
+    let mut keys = Vec::new();
+    if dir.is_dir() {
+        walk(dir, "", &mut keys)?;
+    }
+    Ok(keys)
+}
+
+/// Read the top-level scalar entries of a `meta.yaml` file
+fn read_meta(dir: &Path) -> Option<HashMap<String, String>> {
+    let contents = std::fs::read_to_string(dir.join(META_FILE)).ok()?;
+    Some(parse_meta(&contents))
+}
+
+/// Parse `key: value` lines of a flat YAML mapping, unquoting quoted values.
+/// MLflow's meta files are flat, so nested structures are not needed.
+fn parse_meta(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with([' ', '\t', '#', '-']))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), unquote_yaml(value.trim())))
+        .collect()
+}
+
+fn unquote_yaml(value: &str) -> String {
+    if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        inner.replace("''", "'")
+    } else if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        inner.replace("\\\"", "\"").replace("\\\\", "\\")
+    } else {
+        value.to_string()
+    }
+}
+
+fn is_deleted(meta: &HashMap<String, String>) -> bool {
+    meta.get("lifecycle_stage")
+        .is_some_and(|stage| stage == "deleted")
+}
+
+/// Parse a `<timestamp_ms> <value> <step>` metric line.
+/// Files from old MLflow versions omit the step, which then defaults to 0.
+fn parse_metric_line(line: &str) -> Option<MetricPoint> {
+    let mut fields = line.split_whitespace();
+    let timestamp = fields
+        .next()?
+        .parse::<i64>()
+        .ok()
+        .and_then(DateTime::<Utc>::from_timestamp_millis);
+    let value = fields.next()?.parse::<f64>().ok()?;
+    let step = fields.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+    Some(MetricPoint {
+        step,
+        value,
+        timestamp,
+    })
+}
+
+/// MLflow stores params as strings; recover numbers and booleans for display
+fn parse_param_value(value: &str) -> ConfigValue {
+    if let Ok(i) = value.parse::<i64>() {
+        ConfigValue::Int(i)
+    } else if let Ok(f) = value.parse::<f64>() {
+        ConfigValue::Float(f)
+    } else {
+        match value {
+            "True" | "true" => ConfigValue::Bool(true),
+            "False" | "false" => ConfigValue::Bool(false),
+            "None" => ConfigValue::Null,
+            _ => ConfigValue::String(value.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    /// Create an `mlruns/` tree with one experiment holding two runs and a deleted one
+    fn create_test_store(root: &Path) {
+        write(
+            &root.join("1/meta.yaml"),
+            "artifact_location: file:///mlruns/1\nexperiment_id: '1'\nlifecycle_stage: active\nname: 'vision: resnet'\n",
+        );
+        write(
+            &root.join("1/abcdef0123456789/meta.yaml"),
+            "run_id: abcdef0123456789\nrun_name: bright-owl\nlifecycle_stage: active\nstart_time: 1700000000000\n",
+        );
+        write(
+            &root.join("1/abcdef0123456789/metrics/train/loss"),
+            "1700000001000 0.5 1\n1700000000000 1.0 0\n",
+        );
+        write(
+            &root.join("1/abcdef0123456789/metrics/acc"),
+            "1700000000000 0.25 0\n",
+        );
+        write(&root.join("1/abcdef0123456789/params/lr"), "0.001");
+        write(&root.join("1/abcdef0123456789/params/optimizer"), "adam");
+
+        write(
+            &root.join("1/0011223344556677/meta.yaml"),
+            "run_id: 0011223344556677\nlifecycle_stage: active\nstart_time: 1700000100000\n",
+        );
+        write(
+            &root.join("1/0011223344556677/tags/mlflow.runName"),
+            "calm-fox",
+        );
+
+        write(
+            &root.join("1/deadbeef/meta.yaml"),
+            "run_id: deadbeef\nlifecycle_stage: deleted\n",
+        );
+        write(
+            &root.join("2/meta.yaml"),
+            "name: old\nlifecycle_stage: deleted\n",
+        );
+    }
+
+    #[test]
+    fn test_mlflow_backend_reads_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        create_test_store(dir.path());
+        let backend = MlflowBackend::new(dir.path().to_path_buf());
+
+        let projects = backend.list_projects().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name, "vision: resnet");
+        assert_eq!(projects[0].run_count, 2);
+
+        // Newest run first; names come from meta.yaml or the runName tag
+        let runs = backend.list_runs("vision: resnet").unwrap();
+        let names: Vec<&str> = runs.iter().map(|r| r.display_name.as_str()).collect();
+        assert_eq!(names, vec!["calm-fox", "bright-o..."]);
+        assert_eq!(runs[1].id, "abcdef0123456789");
+
+        let config = &runs[1].config;
+        assert_eq!(config.len(), 2);
+        assert_eq!(config[0].key, "lr");
+        assert!(matches!(config[0].value, ConfigValue::Float(v) if v == 0.001));
+        assert!(matches!(&config[1].value, ConfigValue::String(s) if s == "adam"));
+
+        let metrics = backend
+            .get_all_metrics("vision: resnet", "abcdef0123456789")
+            .unwrap();
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["acc", "train/loss"]);
+        let steps: Vec<i64> = metrics[1].points().iter().map(|p| p.step).collect();
+        assert_eq!(steps, vec![0, 1]);
+        assert_eq!(
+            metrics[1].points()[0].timestamp.unwrap().timestamp_millis(),
+            1_700_000_000_000
+        );
+
+        assert!(backend
+            .get_all_metrics("vision: resnet", "deadbeef")
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_metric_line() {
+        let point = parse_metric_line("1700000000000 nan 3").unwrap();
+        assert_eq!(point.step, 3);
+        assert!(point.value.is_nan());
+
+        // Pre-step format
+        assert_eq!(parse_metric_line("1700000000000 2.5").unwrap().step, 0);
+        assert!(parse_metric_line("garbage").is_none());
+    }
+}
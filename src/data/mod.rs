@@ -1,13 +1,36 @@
-//! Data layer for accessing trackio's SQLite database.
+//! Data layer for accessing experiment data (trackio's SQLite databases by default).
 //!
 //! Handles loading projects, runs, metrics, config, and comparison state.
 
+mod backend;
 mod comparison;
+mod loader;
+mod logs;
+#[cfg(test)]
+mod memory;
+mod mlflow;
 mod models;
+mod parquet;
+mod roots;
 mod storage;
+mod tensorboard;
+mod watcher;
 
+pub use backend::StorageBackend;
 pub use comparison::ComparisonState;
+pub use loader::{LoadRequest, LoadResponse, Loader, MetricsRequest};
+pub use logs::LogFileBackend;
+#[cfg(test)]
+pub(crate) use memory::MemoryBackend;
+pub use mlflow::MlflowBackend;
+pub use models::{
+    Config, ConfigValue, Histogram, Media, Metric, MetricPoint, MetricsUpdate, Project, RichValues,
+    RichValuesUpdate, Run, Table, SYSTEM_METRIC_PREFIX,
+};
 #[cfg(test)]
-pub(crate) use models::MetricPoint;
-pub use models::{Config, Metric, Project, Run};
+pub(crate) use models::{HistogramPoint, MediaPoint, TablePoint, TextPoint, TextSeries};
+pub use parquet::ParquetBackend;
+pub use roots::MultiRootBackend;
 pub use storage::Storage;
+pub use tensorboard::TensorBoardBackend;
+pub use watcher::DbWatcher;
@@ -1,16 +1,45 @@
 //! Data models representing trackio's experiment data.
 
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// A trackio project containing multiple runs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
+    /// Identifier used to load the project's runs; equals `name` unless
+    /// several data roots are merged
+    pub id: String,
     pub name: String,
+    /// Label of the data root the project was found in, when browsing several roots
+    pub root: Option<String>,
     pub run_count: usize,
     pub last_updated: Option<DateTime<Utc>>,
 }
 
+impl Project {
+    /// Create a project identified by its name
+    pub fn new(name: String, run_count: usize, last_updated: Option<DateTime<Utc>>) -> Self {
+        Project {
+            id: name.clone(),
+            name,
+            root: None,
+            run_count,
+            last_updated,
+        }
+    }
+
+    /// Name qualified with its data root, if any (e.g. `mnist @ nfs/trackio`)
+    pub fn qualified_name(&self) -> String {
+        match &self.root {
+            Some(root) => format!("{} @ {root}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
 /// Maximum characters to show in display name before truncating
 const DISPLAY_NAME_MAX_LEN: usize = 8;
 
@@ -23,6 +52,10 @@ pub struct Run {
     pub config: Vec<Config>,
     /// Cached display name (computed on construction, derived from id)
     pub display_name: String,
+    /// Whether the latest logged values include NaN or infinity, as far as the
+    /// backend can tell without loading the run's metrics
+    #[serde(default)]
+    pub diverged: bool,
 }
 
 impl Run {
@@ -33,20 +66,30 @@ impl Run {
         created_at: Option<DateTime<Utc>>,
         config: Vec<Config>,
     ) -> Self {
-        // Truncate long IDs for display
-        let display_name = if id.len() > DISPLAY_NAME_MAX_LEN {
-            format!("{}...", &id[..DISPLAY_NAME_MAX_LEN])
-        } else {
-            id.clone()
-        };
+        let display_name = truncate_display_name(&id);
         Run {
             id,
             project,
             created_at,
             config,
             display_name,
+            diverged: false,
         }
     }
+
+    /// Display a human-readable name instead of the id (e.g. for backends with opaque run ids)
+    pub fn with_display_name(mut self, name: &str) -> Self {
+        self.display_name = truncate_display_name(name);
+        self
+    }
+}
+
+/// Truncate long names for display
+fn truncate_display_name(name: &str) -> String {
+    match name.char_indices().nth(DISPLAY_NAME_MAX_LEN) {
+        Some((idx, _)) => format!("{}...", &name[..idx]),
+        None => name.to_string(),
+    }
 }
 
 /// A configuration key-value pair for a run
@@ -85,18 +128,141 @@ impl std::fmt::Display for ConfigValue {
     }
 }
 
+/// Name prefix of system (hardware) metrics, e.g. `system/gpu0_util`
+pub const SYSTEM_METRIC_PREFIX: &str = "system/";
+
 /// A metric tracked during training (e.g., "train_loss", "accuracy")
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metric {
+    /// Metric name; system metrics are prefixed with `system/`
     pub name: String,
-    pub points: Vec<MetricPoint>,
+    /// Points in step order
+    points: Vec<MetricPoint>,
+    /// Write order of `points`, worked out on first use
+    #[serde(skip)]
+    write_order: OnceLock<WriteOrder>,
+}
+
+/// Indices into a metric's points in write order, so charts redrawn every frame
+/// don't have to sort the points again
+#[derive(Debug, Clone, Default)]
+struct WriteOrder {
+    /// Points of each write segment, oldest first
+    segments: Vec<Vec<usize>>,
+    /// Latest write of each step, in step order
+    latest: Vec<usize>,
+}
+
+impl WriteOrder {
+    fn new(points: &[MetricPoint]) -> Self {
+        let mut by_write: Vec<usize> = (0..points.len()).collect();
+        // Points are in step order, so a stable sort by time recovers the write order
+        by_write.sort_by_key(|&i| points[i].timestamp);
+
+        let mut segments: Vec<Vec<usize>> = Vec::new();
+        for i in by_write {
+            match segments.last_mut() {
+                Some(segment)
+                    if segment
+                        .last()
+                        .is_some_and(|&last| points[i].step >= points[last].step) =>
+                {
+                    segment.push(i)
+                }
+                _ => segments.push(vec![i]),
+            }
+        }
+
+        let latest = if segments.len() <= 1 {
+            (0..points.len()).collect()
+        } else {
+            let mut cutoff = i64::MAX;
+            let mut kept: Vec<usize> = Vec::new();
+            for segment in segments.iter().rev() {
+                kept.extend(segment.iter().filter(|&&i| points[i].step < cutoff));
+                cutoff = cutoff.min(points[segment[0]].step);
+            }
+            kept.sort_by_key(|&i| points[i].step);
+            kept
+        };
+        WriteOrder { segments, latest }
+    }
 }
 
 impl Metric {
     pub fn new(name: String) -> Self {
+        Metric::with_points(name, Vec::new())
+    }
+
+    /// Points in step order
+    pub fn points(&self) -> &[MetricPoint] {
+        &self.points
+    }
+
+    /// Points for changing in place, dropping the cached write order.
+    /// Changes must keep the points in step order.
+    pub fn points_mut(&mut self) -> &mut Vec<MetricPoint> {
+        self.write_order.take();
+        &mut self.points
+    }
+
+    /// Create a metric from points in step order
+    pub fn with_points(name: String, points: Vec<MetricPoint>) -> Self {
         Metric {
             name,
-            points: Vec::new(),
+            points,
+            write_order: OnceLock::new(),
+        }
+    }
+
+    /// Whether this is a system (hardware) metric such as `system/cpu`
+    pub fn is_system(&self) -> bool {
+        self.name.starts_with(SYSTEM_METRIC_PREFIX)
+    }
+
+    /// Points split at each resume (the step going back in write order, as when a
+    /// job restarts from a checkpoint), oldest write first. Write order is taken
+    /// from timestamps; series without them form a single segment.
+    pub fn write_segments(&self) -> Vec<Vec<&MetricPoint>> {
+        self.write_order()
+            .segments
+            .iter()
+            .map(|segment| segment.iter().map(|&i| &self.points[i]).collect())
+            .collect()
+    }
+
+    /// Points in step order where each resume replaces the points previously
+    /// written from its first step on (latest write wins)
+    pub fn latest_writes(&self) -> Vec<&MetricPoint> {
+        self.write_order()
+            .latest
+            .iter()
+            .map(|&i| &self.points[i])
+            .collect()
+    }
+
+    fn write_order(&self) -> &WriteOrder {
+        self.write_order
+            .get_or_init(|| WriteOrder::new(&self.points))
+    }
+
+    /// Whether the latest logged value is NaN or infinite (e.g. a diverged loss)
+    pub fn latest_is_non_finite(&self) -> bool {
+        self.points.last().is_some_and(|p| !p.value.is_finite())
+    }
+
+    /// Append points, keeping the series ordered by step.
+    /// Points are usually logged in step order, so this is a plain push in the common case.
+    pub fn extend(&mut self, points: Vec<MetricPoint>) {
+        self.write_order.take();
+        let check_from = self.points.len().saturating_sub(1);
+        self.points.extend(points);
+        let needs_sort = self.points[check_from..]
+            .windows(2)
+            .any(|w| w[1].step < w[0].step);
+        if needs_sort {
+            // Stable sort keeps write order for duplicate steps
+            self.points.sort_by_key(|p| p.step);
         }
     }
 }
@@ -108,3 +274,315 @@ pub struct MetricPoint {
     pub value: f64,
     pub timestamp: Option<DateTime<Utc>>,
 }
+
+/// A histogram logged at one step: bin edges and the count in each bin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistogramPoint {
+    pub step: i64,
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Bin edges, one more than `counts`
+    pub edges: Vec<f64>,
+    pub counts: Vec<f64>,
+}
+
+/// A histogram series (e.g. a weight or gradient distribution over training)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Histogram {
+    pub name: String,
+    pub points: Vec<HistogramPoint>,
+}
+
+/// A table logged at one step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TablePoint {
+    pub step: i64,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub columns: Vec<String>,
+    /// Cells of each row, one per column
+    pub rows: Vec<Vec<ConfigValue>>,
+}
+
+/// A table series (e.g. sample predictions logged every few epochs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Table {
+    pub name: String,
+    pub points: Vec<TablePoint>,
+}
+
+/// A media file (image, video, audio) logged at one step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaPoint {
+    pub step: i64,
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Media type, e.g. `image`, `video` or `audio`
+    pub kind: String,
+    /// Location of the file on disk
+    pub path: PathBuf,
+    pub caption: Option<String>,
+}
+
+/// A media series (e.g. generated samples logged every few epochs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Media {
+    pub name: String,
+    pub points: Vec<MediaPoint>,
+}
+
+/// A text value (status string, generated sample, note) logged at one step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextPoint {
+    pub step: i64,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub text: String,
+}
+
+/// A text series, shown in the run's log panel rather than as a chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextSeries {
+    pub name: String,
+    pub points: Vec<TextPoint>,
+}
+
+/// Non-scalar values logged for a run, each kind sorted by name
+#[derive(Debug, Clone, Default)]
+pub struct RichValues {
+    pub histograms: Vec<Histogram>,
+    pub tables: Vec<Table>,
+    pub media: Vec<Media>,
+    pub texts: Vec<TextSeries>,
+}
+
+impl RichValues {
+    /// Find a histogram series by name
+    pub fn histogram(&self, name: &str) -> Option<&Histogram> {
+        self.histograms.iter().find(|h| h.name == name)
+    }
+
+    /// Find a table series by name
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    /// Find a media series by name
+    pub fn media(&self, name: &str) -> Option<&Media> {
+        self.media.iter().find(|m| m.name == name)
+    }
+
+    /// Number of logged entries of the named series, of any kind
+    pub fn step_count(&self, name: &str) -> Option<usize> {
+        self.histogram(name)
+            .map(|h| h.points.len())
+            .or_else(|| self.table(name).map(|t| t.points.len()))
+            .or_else(|| self.media(name).map(|m| m.points.len()))
+    }
+
+    /// All text entries as (series name, point), ordered by step and timestamp
+    pub fn text_entries(&self) -> Vec<(&str, &TextPoint)> {
+        let mut entries: Vec<(&str, &TextPoint)> = self
+            .texts
+            .iter()
+            .flat_map(|t| t.points.iter().map(|p| (t.name.as_str(), p)))
+            .collect();
+        // Stable sort keeps name order for entries logged together
+        entries.sort_by_key(|(_, p)| (p.step, p.timestamp));
+        entries
+    }
+
+    /// Names of the charted series (text is shown in the log panel), for
+    /// listing next to scalar metrics
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        let histograms = self.histograms.iter().map(|h| h.name.as_str());
+        let tables = self.tables.iter().map(|t| t.name.as_str());
+        histograms
+            .chain(tables)
+            .chain(self.media.iter().map(|m| m.name.as_str()))
+    }
+}
+
+/// Result of an incremental metrics load for a run
+#[derive(Debug, Clone)]
+pub enum MetricsUpdate {
+    /// Previously loaded series are stale and must be replaced wholesale
+    Reload(Vec<Metric>),
+    /// Points logged since the last load, to be appended to existing series
+    Append(Vec<Metric>),
+}
+
+impl MetricsUpdate {
+    /// Apply this update to a name-sorted list of metrics in place.
+    pub fn apply(self, metrics: &mut Vec<Metric>) {
+        match self {
+            MetricsUpdate::Reload(new_metrics) => *metrics = new_metrics,
+            MetricsUpdate::Append(new_metrics) => {
+                for new_metric in new_metrics {
+                    match metrics.binary_search_by(|m| m.name.cmp(&new_metric.name)) {
+                        Ok(idx) => metrics[idx].extend(new_metric.points),
+                        Err(idx) => metrics.insert(idx, new_metric),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether this update carries no new data
+    pub fn is_empty(&self) -> bool {
+        match self {
+            MetricsUpdate::Reload(_) => false,
+            MetricsUpdate::Append(metrics) => metrics.is_empty(),
+        }
+    }
+}
+
+/// Result of an incremental load of a run's non-scalar values
+#[derive(Debug, Clone)]
+pub enum RichValuesUpdate {
+    /// Previously loaded values are stale and must be replaced wholesale
+    Reload(RichValues),
+    /// Values logged since the last load, to be appended to existing series
+    Append(RichValues),
+}
+
+impl RichValuesUpdate {
+    /// Apply this update to a run's values in place.
+    pub fn apply(self, rich_values: &mut RichValues) {
+        match self {
+            RichValuesUpdate::Reload(new_values) => *rich_values = new_values,
+            RichValuesUpdate::Append(new_values) => {
+                merge_series(
+                    &mut rich_values.histograms,
+                    new_values.histograms,
+                    |h| &h.name,
+                    |h, new| h.points.extend(new.points),
+                );
+                merge_series(
+                    &mut rich_values.tables,
+                    new_values.tables,
+                    |t| &t.name,
+                    |t, new| t.points.extend(new.points),
+                );
+                merge_series(
+                    &mut rich_values.media,
+                    new_values.media,
+                    |m| &m.name,
+                    |m, new| m.points.extend(new.points),
+                );
+                merge_series(
+                    &mut rich_values.texts,
+                    new_values.texts,
+                    |t| &t.name,
+                    |t, new| t.points.extend(new.points),
+                );
+            }
+        }
+    }
+
+    /// Whether this update carries no new data
+    pub fn is_empty(&self) -> bool {
+        match self {
+            RichValuesUpdate::Reload(_) => false,
+            RichValuesUpdate::Append(values) => {
+                values.histograms.is_empty()
+                    && values.tables.is_empty()
+                    && values.media.is_empty()
+                    && values.texts.is_empty()
+            }
+        }
+    }
+}
+
+/// Append new series points to a name-sorted list of series, adding unseen names in order
+fn merge_series<T>(
+    series: &mut Vec<T>,
+    new_series: Vec<T>,
+    name: impl Fn(&T) -> &str,
+    append: impl Fn(&mut T, T),
+) {
+    for new in new_series {
+        match series.binary_search_by(|s| name(s).cmp(name(&new))) {
+            Ok(idx) => append(&mut series[idx], new),
+            Err(idx) => series.insert(idx, new),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_extend_keeps_step_order() {
+        let point = |step| MetricPoint {
+            step,
+            value: 0.0,
+            timestamp: None,
+        };
+        let mut metric = Metric::new("loss".to_string());
+        metric.extend(vec![point(0), point(2)]);
+        metric.extend(vec![point(1), point(3)]);
+        let steps: Vec<i64> = metric.points.iter().map(|p| p.step).collect();
+        assert_eq!(steps, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_resumed_run_segments() {
+        // Steps 0-3 logged, then the job resumed from step 2 and went on to 4
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut metric = Metric::new("loss".to_string());
+        metric.extend(
+            [
+                (0, 1.0),
+                (1, 0.9),
+                (2, 0.8),
+                (3, 0.7),
+                (2, 0.5),
+                (3, 0.4),
+                (4, 0.3),
+            ]
+            .iter()
+            .enumerate()
+            .map(|(i, &(step, value))| MetricPoint {
+                step,
+                value,
+                timestamp: Some(start + chrono::Duration::seconds(i as i64)),
+            })
+            .collect(),
+        );
+        let values =
+            |points: &[&MetricPoint]| -> Vec<f64> { points.iter().map(|p| p.value).collect() };
+
+        let segments = metric.write_segments();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(values(&segments[0]), vec![1.0, 0.9, 0.8, 0.7]);
+        assert_eq!(values(&segments[1]), vec![0.5, 0.4, 0.3]);
+        assert_eq!(
+            values(&metric.latest_writes()),
+            vec![1.0, 0.9, 0.5, 0.4, 0.3]
+        );
+
+        // Without timestamps the write order is unknown
+        let points = metric
+            .points
+            .iter()
+            .map(|p| MetricPoint {
+                timestamp: None,
+                ..p.clone()
+            })
+            .collect();
+        let mut metric = Metric::with_points("loss".to_string(), points);
+        assert_eq!(metric.write_segments().len(), 1);
+        assert_eq!(metric.latest_writes().len(), 7);
+
+        // Appended points are taken into account
+        metric.extend(vec![MetricPoint {
+            step: 1,
+            value: 0.0,
+            timestamp: None,
+        }]);
+        assert_eq!(metric.latest_writes().len(), 8);
+
+        // As are points changed in place
+        metric.points_mut().truncate(2);
+        assert_eq!(metric.write_segments().concat().len(), 2);
+        assert_eq!(metric.latest_writes().len(), 2);
+    }
+}
@@ -0,0 +1,575 @@
+//! Parquet storage backend for trackio's dataset exports.
+//!
+//! When syncing to a dataset, trackio mirrors each project database to Parquet:
+//! - `<project>.parquet`: one row per `metrics` row, with `id`, `timestamp`,
+//!   `run_name`, `step` columns and one column per logged metric
+//! - `<project>_configs.parquet` (optional): `run_name`, `config` (JSON), `created_at`
+//! - `<project>_system.parquet` (optional): like the metrics table, with system
+//!   metrics and no `step` column
+//!
+//! Archives are read-only, so each project's files are parsed once and cached
+//! until one of their modification times changes.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::{Field, Row};
+
+use super::backend::StorageBackend;
+use super::models::{Metric, MetricPoint, Project, Run, SYSTEM_METRIC_PREFIX};
+use super::storage::{parse_config_json, parse_timestamp};
+
+/// File name suffix of the per-project config table
+const CONFIGS_SUFFIX: &str = "_configs";
+
+/// File name suffix of the per-project system metrics table
+const SYSTEM_SUFFIX: &str = "_system";
+
+/// Suffixes of auxiliary tables, which are not projects themselves when they sit
+/// next to the `<project>.parquet` they belong to
+const AUXILIARY_SUFFIXES: [&str; 2] = [CONFIGS_SUFFIX, SYSTEM_SUFFIX];
+
+/// Columns of the metrics table that are not metrics
+const RESERVED_COLUMNS: [&str; 5] = ["id", "timestamp", "run_name", "step", "__index_level_0__"];
+
+/// Modification times of a project's metrics, configs and system tables
+type ProjectSignature = [Option<SystemTime>; 3];
+
+/// Cached project parses, keyed by name, with the file signature they were parsed at
+type ProjectCache = HashMap<String, (ProjectSignature, Arc<ProjectData>)>;
+
+/// A fully parsed project export
+#[derive(Debug, Default)]
+struct ProjectData {
+    runs: Vec<Run>,
+    /// Metrics per run, sorted by name
+    metrics: HashMap<String, Vec<Metric>>,
+    /// System metrics per run, named with the `system/` prefix and sorted by name
+    system_metrics: HashMap<String, Vec<Metric>>,
+}
+
+/// Metric series read from a metrics or system metrics table
+#[derive(Debug, Default)]
+struct MetricsTable {
+    /// Series per run, sorted by name
+    metrics: HashMap<String, Vec<Metric>>,
+    /// Earliest timestamp of each run
+    first_seen: HashMap<String, Option<DateTime<Utc>>>,
+}
+
+/// Storage backend reading a directory of trackio Parquet exports
+pub struct ParquetBackend {
+    root: PathBuf,
+    cache: RefCell<ProjectCache>,
+}
+
+impl ParquetBackend {
+    /// Create a backend reading `<project>.parquet` files from `root`
+    pub fn new(root: PathBuf) -> Self {
+        ParquetBackend {
+            root,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn project_path(&self, project: &str, suffix: &str) -> PathBuf {
+        self.root.join(format!("{project}{suffix}.parquet"))
+    }
+
+    /// Whether a file stem names an auxiliary table of another project
+    fn is_auxiliary(&self, name: &str) -> bool {
+        AUXILIARY_SUFFIXES.iter().any(|suffix| {
+            name.strip_suffix(suffix)
+                .is_some_and(|project| self.project_path(project, "").exists())
+        })
+    }
+
+    /// Load a project, reusing the cached copy if the file is unchanged
+    fn load_project(&self, project: &str) -> Result<Arc<ProjectData>> {
+        let path = self.project_path(project, "");
+        if !path.exists() {
+            anyhow::bail!("Project file not found: {path:?}");
+        }
+        let modified = |suffix: &str| {
+            std::fs::metadata(self.project_path(project, suffix))
+                .and_then(|m| m.modified())
+                .ok()
+        };
+        let signature = [
+            modified(""),
+            modified(CONFIGS_SUFFIX),
+            modified(SYSTEM_SUFFIX),
+        ];
+
+        if let Some((cached_at, data)) = self.cache.borrow().get(project) {
+            if *cached_at == signature && signature[0].is_some() {
+                return Ok(Arc::clone(data));
+            }
+        }
+
+        let data = Arc::new(self.parse_project(project)?);
+        self.cache
+            .borrow_mut()
+            .insert(project.to_string(), (signature, Arc::clone(&data)));
+        Ok(data)
+    }
+
+    fn parse_project(&self, project: &str) -> Result<ProjectData> {
+        let MetricsTable {
+            metrics,
+            first_seen,
+        } = parse_metrics_table(&self.project_path(project, ""), false)?;
+
+        let system_path = self.project_path(project, SYSTEM_SUFFIX);
+        let system_metrics = if system_path.exists() {
+            parse_metrics_table(&system_path, true)?.metrics
+        } else {
+            HashMap::new()
+        };
+
+        let mut runs = self.parse_configs(project)?;
+
+        // Runs without a config row still show up, dated by their first metric
+        for (run_name, created_at) in first_seen {
+            if !runs.iter().any(|r| r.id == run_name) {
+                runs.push(Run::new(
+                    run_name,
+                    project.to_string(),
+                    created_at,
+                    Vec::new(),
+                ));
+            }
+        }
+        // Most recent first, like the SQLite backend
+        runs.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+
+        Ok(ProjectData {
+            runs,
+            metrics,
+            system_metrics,
+        })
+    }
+
+    /// Read runs from the optional configs table
+    fn parse_configs(&self, project: &str) -> Result<Vec<Run>> {
+        let path = self.project_path(project, CONFIGS_SUFFIX);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut runs = Vec::new();
+        for row in read_rows(&path)? {
+            let mut run_name = None;
+            let mut config = Vec::new();
+            let mut created_at = None;
+            for (name, field) in row.get_column_iter() {
+                match name.as_str() {
+                    "run_name" => run_name = field_to_string(field),
+                    "config" => {
+                        config = field_to_string(field)
+                            .and_then(|json| parse_config_json(&json).ok())
+                            .unwrap_or_default()
+                    }
+                    "created_at" => created_at = field_to_timestamp(field),
+                    _ => {}
+                }
+            }
+            if let Some(run_name) = run_name {
+                runs.push(Run::new(run_name, project.to_string(), created_at, config));
+            }
+        }
+        Ok(runs)
+    }
+}
+
+impl StorageBackend for ParquetBackend {
+    fn list_projects(&self) -> Result<Vec<Project>> {
+        let mut projects = Vec::new();
+
+        if !self.root.exists() {
+            return Ok(projects);
+        }
+
+        let entries = std::fs::read_dir(&self.root)
+            .with_context(|| format!("Failed to read directory: {:?}", self.root))?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map(|e| e == "parquet").unwrap_or(false) {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    if name.starts_with('.') || self.is_auxiliary(name) {
+                        continue;
+                    }
+
+                    let (run_count, last_updated) = self
+                        .load_project(name)
+                        .map(|data| {
+                            let last = data.runs.iter().filter_map(|r| r.created_at).max();
+                            (data.runs.len(), last)
+                        })
+                        .unwrap_or((0, None));
+
+                    projects.push(Project::new(name.to_string(), run_count, last_updated));
+                }
+            }
+        }
+
+        // Sort by last updated (most recent first)
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_updated));
+
+        Ok(projects)
+    }
+
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
+        Ok(self.load_project(project)?.runs.clone())
+    }
+
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        Ok(self
+            .load_project(project)?
+            .metrics
+            .get(run_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Get system metrics from the `<project>_system.parquet` table, if there is one
+    fn get_system_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        Ok(self
+            .load_project(project)?
+            .system_metrics
+            .get(run_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// Read the metric series of a metrics table. System tables have no training
+/// step, so each run's sample index is used instead, and their series are named
+/// with the `system/` prefix.
+fn parse_metrics_table(path: &Path, system: bool) -> Result<MetricsTable> {
+    let mut metrics_by_run: HashMap<String, HashMap<String, Metric>> = HashMap::new();
+    let mut first_seen: HashMap<String, Option<DateTime<Utc>>> = HashMap::new();
+    let mut samples: HashMap<String, i64> = HashMap::new();
+
+    for row in read_rows(path)? {
+        let mut run_name = None;
+        let mut step = None;
+        let mut timestamp = None;
+        let mut values = Vec::new();
+
+        for (name, field) in row.get_column_iter() {
+            match name.as_str() {
+                "run_name" => run_name = field_to_string(field),
+                "step" => step = field_to_f64(field).map(|s| s as i64),
+                "timestamp" => timestamp = field_to_timestamp(field),
+                name if RESERVED_COLUMNS.contains(&name) => {}
+                name => {
+                    if let Some(value) = field_to_f64(field) {
+                        values.push((name.to_string(), value));
+                    }
+                }
+            }
+        }
+
+        let Some(run_name) = run_name else {
+            continue;
+        };
+        let step = if system {
+            let sample = samples.entry(run_name.clone()).or_default();
+            *sample += 1;
+            *sample - 1
+        } else {
+            let Some(step) = step else {
+                continue;
+            };
+            step
+        };
+        let run_start = first_seen.entry(run_name.clone()).or_insert(timestamp);
+        if let Some(ts) = timestamp {
+            if run_start.is_none_or(|start| ts < start) {
+                *run_start = Some(ts);
+            }
+        }
+
+        let run_metrics = metrics_by_run.entry(run_name).or_default();
+        for (name, value) in values {
+            let name = if system && !name.starts_with(SYSTEM_METRIC_PREFIX) {
+                format!("{SYSTEM_METRIC_PREFIX}{name}")
+            } else {
+                name
+            };
+            run_metrics
+                .entry(name.clone())
+                .or_insert_with(|| Metric::new(name))
+                .points_mut()
+                .push(MetricPoint {
+                    step,
+                    value,
+                    timestamp,
+                });
+        }
+    }
+
+    let metrics = metrics_by_run
+        .into_iter()
+        .map(|(run_name, metrics)| {
+            let mut metrics: Vec<Metric> = metrics.into_values().collect();
+            metrics.sort_by(|a, b| a.name.cmp(&b.name));
+            for metric in &mut metrics {
+                metric.points_mut().sort_by_key(|p| p.step);
+            }
+            (run_name, metrics)
+        })
+        .collect();
+
+    Ok(MetricsTable {
+        metrics,
+        first_seen,
+    })
+}
+
+/// Read all rows of a Parquet file
+fn read_rows(path: &Path) -> Result<Vec<Row>> {
+    let file = File::open(path).with_context(|| format!("Failed to open file: {path:?}"))?;
+    let reader = SerializedFileReader::new(file)
+        .with_context(|| format!("Failed to read Parquet file: {path:?}"))?;
+    let rows = reader
+        .get_row_iter(None)?
+        .collect::<parquet::errors::Result<Vec<Row>>>()
+        .with_context(|| format!("Failed to read rows: {path:?}"))?;
+    Ok(rows)
+}
+
+/// Numeric value of a field, if it holds a number
+fn field_to_f64(field: &Field) -> Option<f64> {
+    match field {
+        Field::Bool(v) => Some(f64::from(u8::from(*v))),
+        Field::Byte(v) => Some(f64::from(*v)),
+        Field::Short(v) => Some(f64::from(*v)),
+        Field::Int(v) => Some(f64::from(*v)),
+        Field::Long(v) => Some(*v as f64),
+        Field::UByte(v) => Some(f64::from(*v)),
+        Field::UShort(v) => Some(f64::from(*v)),
+        Field::UInt(v) => Some(f64::from(*v)),
+        Field::ULong(v) => Some(*v as f64),
+        Field::Float16(v) => Some(f64::from(*v)),
+        Field::Float(v) => Some(f64::from(*v)),
+        Field::Double(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// String value of a field, if it holds text
+fn field_to_string(field: &Field) -> Option<String> {
+    match field {
+        Field::Str(s) => Some(s.clone()),
+        Field::Bytes(b) => b.as_utf8().ok().map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Timestamp of a field stored either as text or as a Parquet timestamp
+fn field_to_timestamp(field: &Field) -> Option<DateTime<Utc>> {
+    match field {
+        Field::TimestampMillis(ms) => DateTime::from_timestamp_millis(*ms),
+        Field::TimestampMicros(us) => DateTime::from_timestamp_micros(*us),
+        _ => parse_timestamp(field_to_string(field)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    /// A column of test data: values with `None` for nulls
+    enum Column {
+        Long(Vec<i64>),
+        Text(Vec<&'static str>),
+        Double(Vec<Option<f64>>),
+    }
+
+    /// Write a single row group Parquet file; columns must match the schema order
+    fn write_parquet(path: &Path, schema: &str, columns: Vec<Column>) {
+        let schema = Arc::new(parse_message_type(schema).unwrap());
+        let file = File::create(path).unwrap();
+        let mut writer = SerializedFileWriter::new(file, schema, Default::default()).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+        for column in columns {
+            let mut col = row_group.next_column().unwrap().unwrap();
+            match column {
+                Column::Long(values) => {
+                    col.typed::<Int64Type>()
+                        .write_batch(&values, None, None)
+                        .unwrap();
+                }
+                Column::Text(values) => {
+                    let values: Vec<ByteArray> = values.into_iter().map(ByteArray::from).collect();
+                    col.typed::<ByteArrayType>()
+                        .write_batch(&values, None, None)
+                        .unwrap();
+                }
+                Column::Double(values) => {
+                    let def_levels: Vec<i16> =
+                        values.iter().map(|v| i16::from(v.is_some())).collect();
+                    let present: Vec<f64> = values.into_iter().flatten().collect();
+                    col.typed::<DoubleType>()
+                        .write_batch(&present, Some(&def_levels), None)
+                        .unwrap();
+                }
+            }
+            col.close().unwrap();
+        }
+        row_group.close().unwrap();
+        writer.close().unwrap();
+    }
+
+    fn write_test_project(dir: &Path) {
+        write_parquet(
+            &dir.join("proj.parquet"),
+            "message schema {
+                REQUIRED INT64 id;
+                REQUIRED BYTE_ARRAY timestamp (UTF8);
+                REQUIRED BYTE_ARRAY run_name (UTF8);
+                REQUIRED INT64 step;
+                OPTIONAL DOUBLE loss;
+                OPTIONAL DOUBLE accuracy;
+            }",
+            vec![
+                Column::Long(vec![1, 2, 3]),
+                Column::Text(vec![
+                    "2025-01-01T00:00:00",
+                    "2025-01-01T00:01:00",
+                    "2025-01-02T00:00:00",
+                ]),
+                Column::Text(vec!["run-a", "run-a", "run-b"]),
+                Column::Long(vec![0, 1, 0]),
+                Column::Double(vec![Some(1.0), Some(0.5), Some(2.0)]),
+                Column::Double(vec![None, Some(0.9), None]),
+            ],
+        );
+        write_parquet(
+            &dir.join("proj_configs.parquet"),
+            "message schema {
+                REQUIRED BYTE_ARRAY run_name (UTF8);
+                REQUIRED BYTE_ARRAY config (UTF8);
+                REQUIRED BYTE_ARRAY created_at (UTF8);
+            }",
+            vec![
+                Column::Text(vec!["run-a"]),
+                Column::Text(vec![r#"{"lr": 0.01, "_Created": "x"}"#]),
+                Column::Text(vec!["2025-01-01T00:00:00"]),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_lists_projects_and_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_project(dir.path());
+        let backend = ParquetBackend::new(dir.path().to_path_buf());
+
+        // The configs table is not a project of its own
+        let projects = backend.list_projects().unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name, "proj");
+        assert_eq!(projects[0].run_count, 2);
+
+        // Unless no project it could belong to exists
+        std::fs::copy(
+            dir.path().join("proj.parquet"),
+            dir.path().join("load_system.parquet"),
+        )
+        .unwrap();
+        let mut names: Vec<String> = backend
+            .list_projects()
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["load_system", "proj"]);
+
+        let runs = backend.list_runs("proj").unwrap();
+        let ids: Vec<&str> = runs.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["run-b", "run-a"]);
+        let run_a = runs.iter().find(|r| r.id == "run-a").unwrap();
+        assert_eq!(run_a.config.len(), 1);
+        assert_eq!(run_a.config[0].key, "lr");
+    }
+
+    #[test]
+    fn test_loads_metrics_skipping_nulls() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_project(dir.path());
+        let backend = ParquetBackend::new(dir.path().to_path_buf());
+
+        let metrics = backend.get_all_metrics("proj", "run-a").unwrap();
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["accuracy", "loss"]);
+        assert_eq!(metrics[0].points().len(), 1);
+        assert_eq!(metrics[0].points()[0].step, 1);
+        assert_eq!(metrics[1].points().len(), 2);
+        assert!(metrics[1].points()[0].timestamp.is_some());
+
+        assert!(backend.get_all_metrics("missing", "run-a").is_err());
+    }
+
+    #[test]
+    fn test_loads_system_metrics() {
+        let dir = tempfile::tempdir().unwrap();
+        write_test_project(dir.path());
+        let backend = ParquetBackend::new(dir.path().to_path_buf());
+        assert!(backend
+            .get_system_metrics("proj", "run-a")
+            .unwrap()
+            .is_empty());
+
+        write_parquet(
+            &dir.path().join("proj_system.parquet"),
+            "message schema {
+                REQUIRED INT64 id;
+                REQUIRED BYTE_ARRAY timestamp (UTF8);
+                REQUIRED BYTE_ARRAY run_name (UTF8);
+                OPTIONAL DOUBLE cpu;
+                OPTIONAL DOUBLE gpu;
+            }",
+            vec![
+                Column::Long(vec![1, 2, 3]),
+                Column::Text(vec![
+                    "2025-01-01T00:00:00",
+                    "2025-01-01T00:00:10",
+                    "2025-01-01T00:00:10",
+                ]),
+                Column::Text(vec!["run-a", "run-b", "run-a"]),
+                Column::Double(vec![Some(10.0), Some(99.0), Some(20.0)]),
+                Column::Double(vec![Some(1.0), None, None]),
+            ],
+        );
+
+        // Not a project of its own, and picked up without a restart
+        assert_eq!(backend.list_projects().unwrap().len(), 1);
+        let metrics = backend.get_system_metrics("proj", "run-a").unwrap();
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["system/cpu", "system/gpu"]);
+        // Samples are numbered per run in logging order
+        let steps: Vec<i64> = metrics[0].points().iter().map(|p| p.step).collect();
+        assert_eq!(steps, vec![0, 1]);
+        assert_eq!(metrics[0].points()[1].value, 20.0);
+        assert!(backend
+            .get_all_metrics("proj", "run-a")
+            .unwrap()
+            .iter()
+            .all(|m| !m.is_system()));
+    }
+}
@@ -0,0 +1,196 @@
+//! Merging several data roots into one backend.
+//!
+//! Runs may be spread over a local cache, shared network directories and
+//! synced copies. Each root gets its own backend; their projects are merged,
+//! labelled with the root they came from, and given ids that route later
+//! loads back to the right root.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::backend::StorageBackend;
+use super::models::{Metric, MetricsUpdate, Project, RichValues, RichValuesUpdate, Run};
+
+/// Storage backend merging the projects of several data roots
+pub struct MultiRootBackend {
+    roots: Vec<Root>,
+}
+
+struct Root {
+    label: String,
+    backend: Box<dyn StorageBackend>,
+}
+
+impl MultiRootBackend {
+    /// Merge backends reading the given root directories
+    pub fn new(roots: Vec<(PathBuf, Box<dyn StorageBackend>)>) -> Self {
+        let paths: Vec<&Path> = roots.iter().map(|(path, _)| path.as_path()).collect();
+        let labels = root_labels(&paths);
+        MultiRootBackend {
+            roots: labels
+                .into_iter()
+                .zip(roots)
+                .map(|(label, (_, backend))| Root { label, backend })
+                .collect(),
+        }
+    }
+
+    /// Split a merged project id into its root and the root-local project name
+    fn resolve<'a>(&self, project: &'a str) -> Result<(&Root, &'a str)> {
+        project
+            .split_once(':')
+            .and_then(|(idx, name)| Some((self.roots.get(idx.parse::<usize>().ok()?)?, name)))
+            .with_context(|| format!("Project not found: {project}"))
+    }
+}
+
+impl StorageBackend for MultiRootBackend {
+    fn list_projects(&self) -> Result<Vec<Project>> {
+        let mut projects = Vec::new();
+        for (idx, root) in self.roots.iter().enumerate() {
+            // A missing or unreadable root (e.g. an unmounted share) shouldn't hide the others
+            let Ok(root_projects) = root.backend.list_projects() else {
+                continue;
+            };
+            projects.extend(root_projects.into_iter().map(|p| Project {
+                id: format!("{idx}:{}", p.id),
+                root: Some(root.label.clone()),
+                ..p
+            }));
+        }
+
+        // Sort by last updated (most recent first)
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_updated));
+        Ok(projects)
+    }
+
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
+        let (root, name) = self.resolve(project)?;
+        let mut runs = root.backend.list_runs(name)?;
+        for run in &mut runs {
+            run.project = project.to_string();
+        }
+        Ok(runs)
+    }
+
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        let (root, name) = self.resolve(project)?;
+        root.backend.get_all_metrics(name, run_id)
+    }
+
+    fn get_new_metrics(&self, project: &str, run_id: &str) -> Result<MetricsUpdate> {
+        let (root, name) = self.resolve(project)?;
+        root.backend.get_new_metrics(name, run_id)
+    }
+
+    fn get_system_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        let (root, name) = self.resolve(project)?;
+        root.backend.get_system_metrics(name, run_id)
+    }
+
+    fn get_rich_values(&self, project: &str, run_id: &str) -> Result<RichValues> {
+        let (root, name) = self.resolve(project)?;
+        root.backend.get_rich_values(name, run_id)
+    }
+
+    fn get_new_rich_values(&self, project: &str, run_id: &str) -> Result<RichValuesUpdate> {
+        let (root, name) = self.resolve(project)?;
+        root.backend.get_new_rich_values(name, run_id)
+    }
+}
+
+/// Short, distinct labels for root directories: the fewest trailing path
+/// components that tell all roots apart (e.g. `cache/trackio`, `nfs/trackio`)
+fn root_labels(paths: &[&Path]) -> Vec<String> {
+    let components: Vec<Vec<String>> = paths
+        .iter()
+        .map(|path| {
+            path.components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .filter(|c| c != "/")
+                .collect()
+        })
+        .collect();
+    let max_len = components.iter().map(Vec::len).max().unwrap_or(0);
+
+    let suffixes = |len: usize| -> Vec<String> {
+        components
+            .iter()
+            .map(|parts| parts[parts.len().saturating_sub(len)..].join("/"))
+            .collect()
+    };
+    for len in 1..=max_len {
+        let labels = suffixes(len);
+        let mut unique = labels.clone();
+        unique.sort();
+        unique.dedup();
+        if unique.len() == labels.len() {
+            return labels;
+        }
+    }
+    paths.iter().map(|p| p.display().to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MemoryBackend;
+
+    fn backend_with(project: &str, run: &str) -> Box<dyn StorageBackend> {
+        let mut backend = MemoryBackend::new();
+        backend.add_run(
+            Run::new(run.to_string(), project.to_string(), None, Vec::new()),
+            Vec::new(),
+        );
+        Box::new(backend)
+    }
+
+    #[test]
+    fn test_root_labels() {
+        let labels = root_labels(&[
+            Path::new("/home/me/.cache/trackio"),
+            Path::new("/mnt/nfs/trackio"),
+            Path::new("/data/node1"),
+        ]);
+        assert_eq!(labels, vec![".cache/trackio", "nfs/trackio", "data/node1"]);
+
+        assert_eq!(
+            root_labels(&[Path::new("/a"), Path::new("/b")]),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_merges_same_named_projects() {
+        let backend = MultiRootBackend::new(vec![
+            (
+                PathBuf::from("/local/trackio"),
+                backend_with("mnist", "local-run"),
+            ),
+            (
+                PathBuf::from("/nfs/trackio"),
+                backend_with("mnist", "shared-run"),
+            ),
+        ]);
+
+        let projects = backend.list_projects().unwrap();
+        assert_eq!(projects.len(), 2);
+        assert!(projects.iter().all(|p| p.name == "mnist"));
+        assert_ne!(projects[0].id, projects[1].id);
+
+        let shared = projects
+            .iter()
+            .find(|p| p.root.as_deref() == Some("nfs/trackio"))
+            .unwrap();
+        assert_eq!(shared.qualified_name(), "mnist @ nfs/trackio");
+
+        let runs = backend.list_runs(&shared.id).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].id, "shared-run");
+        assert_eq!(runs[0].project, shared.id);
+
+        assert!(backend.get_all_metrics(&shared.id, "shared-run").is_ok());
+        assert!(backend.list_runs("mnist").is_err());
+    }
+}
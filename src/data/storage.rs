@@ -1,19 +1,31 @@
-//! SQLite storage layer for reading trackio's experiment database.
+//! SQLite storage backend for reading trackio's experiment database.
 //!
 //! Trackio database schema:
 //! - One .db file per project in ~/.cache/huggingface/trackio/
 //! - `metrics` table: id, timestamp, run_name, step, metrics (JSON)
 //! - `configs` table: id, run_name, config (JSON), created_at
+//! - `system_metrics` table (optional): id, timestamp, run_name, metrics (JSON)
+//! - Logged media files under `media/`, referenced from the metrics JSON
+//!
+//! Each database's tables are introspected when it is opened, and queries are
+//! picked for the matching schema generation (see `SchemaVersion`). Unknown
+//! layouts fail with an "unsupported schema" error instead of showing nothing.
 
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use rusqlite::{Connection, OpenFlags, Row};
 
-use super::models::{Config, ConfigValue, Metric, MetricPoint, Project, Run};
+use super::backend::StorageBackend;
+use super::models::{
+    Config, ConfigValue, Histogram, HistogramPoint, Media, MediaPoint, Metric, MetricPoint,
+    MetricsUpdate, Project, RichValues, RichValuesUpdate, Run, Table, TablePoint, TextPoint,
+    TextSeries, SYSTEM_METRIC_PREFIX,
+};
 
 /// Helper to read a column that might be stored as TEXT or BLOB
 /// Trackio uses orjson which can write JSON as bytes (BLOB) rather than text
@@ -35,22 +47,238 @@ fn get_string_or_blob(row: &Row, idx: usize) -> rusqlite::Result<String> {
     }
 }
 
+/// Tokens Python's `json` module writes for non-finite floats, with their values
+const NON_FINITE_TOKENS: [(&str, f64); 3] = [
+    ("-Infinity", f64::NEG_INFINITY),
+    ("Infinity", f64::INFINITY),
+    ("NaN", f64::NAN),
+];
+
+/// Marks the strings `quote_non_finite` makes of non-finite tokens, written as a
+/// JSON escape and read back as a leading NUL, so logged text like "NaN" stays text.
+/// NULs already in logged strings are doubled, so no logged string can match.
+const NON_FINITE_SENTINEL: char = '\0';
+
+/// JSON escape of `NON_FINITE_SENTINEL`
+const SENTINEL_ESCAPE: &str = "\\u0000";
+
+/// Parse a JSON object of logged values. The bare `NaN`, `Infinity` and
+/// `-Infinity` tokens Python writes are not valid JSON, so they are quoted
+/// first (behind `NON_FINITE_SENTINEL`) and read back by `metric_value`.
+pub(super) fn parse_metrics_json(json: &str) -> Option<HashMap<String, serde_json::Value>> {
+    serde_json::from_str(&quote_non_finite(json)).ok()
+}
+
+/// Quote non-finite number tokens outside of strings, prefixed with `NON_FINITE_SENTINEL`,
+/// and double the sentinel escapes inside strings
+fn quote_non_finite(json: &str) -> Cow<'_, str> {
+    if !json.contains("NaN") && !json.contains("Infinity") && !json.contains(SENTINEL_ESCAPE) {
+        return Cow::Borrowed(json);
+    }
+
+    let mut quoted = String::with_capacity(json.len() + 8);
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut rest = json;
+    while let Some(c) = rest.chars().next() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if rest.starts_with(SENTINEL_ESCAPE) {
+                quoted.push_str(SENTINEL_ESCAPE);
+                quoted.push_str(SENTINEL_ESCAPE);
+                rest = &rest[SENTINEL_ESCAPE.len()..];
+                continue;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if let Some((token, _)) = NON_FINITE_TOKENS.iter().find(|(t, _)| rest.starts_with(t))
+        {
+            quoted.push('"');
+            quoted.push_str(SENTINEL_ESCAPE);
+            quoted.push_str(token);
+            quoted.push('"');
+            rest = &rest[token.len()..];
+            continue;
+        }
+        quoted.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Cow::Owned(quoted)
+}
+
+/// Numeric value of a logged value: a JSON number, or a non-finite token
+/// quoted by `parse_metrics_json`
+pub(super) fn metric_value(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::String(s) => {
+            let token = s.strip_prefix(NON_FINITE_SENTINEL)?;
+            NON_FINITE_TOKENS
+                .iter()
+                .find(|(t, _)| *t == token)
+                .map(|&(_, v)| v)
+        }
+        value => value.as_f64(),
+    }
+}
+
 /// Parse a timestamp string into a DateTime<Utc>
-fn parse_timestamp(timestamp: Option<String>) -> Option<DateTime<Utc>> {
+/// Timestamps without an offset (Python's naive `isoformat()`) are taken as UTC
+pub(super) fn parse_timestamp(timestamp: Option<String>) -> Option<DateTime<Utc>> {
     timestamp.and_then(|t| {
         DateTime::parse_from_rfc3339(&t)
-            .or_else(|_| DateTime::parse_from_str(&t, "%Y-%m-%dT%H:%M:%S%.f"))
             .map(|dt| dt.with_timezone(&Utc))
+            .or_else(|_| {
+                NaiveDateTime::parse_from_str(&t, "%Y-%m-%dT%H:%M:%S%.f").map(|dt| dt.and_utc())
+            })
             .ok()
     })
 }
 
+/// Position of the last `metrics` row read for a run, used for incremental loads
+#[derive(Debug, Clone, Copy, Default)]
+struct MetricsCursor {
+    /// Highest `metrics.id` seen so far
+    last_id: i64,
+    /// Number of rows seen so far (used to detect deleted rows)
+    row_count: usize,
+}
+
+/// Filter for `metrics` rows that may hold non-scalar values (histograms, tables, media,
+/// text), so scalar-only rows are skipped without parsing their JSON.
+/// Any string value is text, so runs logging text every step match on every row;
+/// the rich values cursor keeps each row from being parsed more than once.
+const RICH_VALUES_FILTER: &str = "(metrics LIKE '%\"bins\"%' \
+     OR metrics LIKE '%\"\\_type\"%' ESCAPE '\\' \
+     OR metrics LIKE '%\"columns\"%' OR metrics LIKE '%\":\"%' OR metrics LIKE '%\": \"%')";
+
+/// Columns the `metrics` table must have in every supported schema
+/// (metric queries are the same for all of them)
+const METRICS_COLUMNS: &[&str] = &["id", "timestamp", "run_name", "step", "metrics"];
+
+/// Columns the `configs` table must have when present
+const CONFIGS_COLUMNS: &[&str] = &["run_name", "config", "created_at"];
+
+/// Columns the optional `system_metrics` table must have to be read
+const SYSTEM_METRICS_COLUMNS: &[&str] = &["id", "timestamp", "run_name", "metrics"];
+
+/// Known generations of trackio's project database layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SchemaVersion {
+    /// Only a `metrics` table (older trackio releases): runs are derived from
+    /// metric rows and have no config
+    MetricsOnly,
+    /// `metrics` plus a `configs` table holding one row per run
+    WithConfigs,
+}
+
+/// SQL for reading a project database of one schema generation
+struct QuerySet {
+    /// `SELECT COUNT(DISTINCT run_name), MAX(<time>)`
+    project_stats: &'static str,
+    /// `SELECT run_name, created_at`, newest first
+    list_runs: &'static str,
+    /// `SELECT run_name, config`, oldest first; `None` when the schema has no
+    /// configs, so runs have an empty config
+    run_configs: Option<&'static str>,
+}
+
+const METRICS_ONLY_QUERIES: QuerySet = QuerySet {
+    project_stats: "SELECT COUNT(DISTINCT run_name), MAX(timestamp) FROM metrics",
+    list_runs: "SELECT run_name, MIN(timestamp) AS created_at FROM metrics \
+                GROUP BY run_name ORDER BY created_at DESC",
+    run_configs: None,
+};
+
+const WITH_CONFIGS_QUERIES: QuerySet = QuerySet {
+    project_stats: "SELECT COUNT(DISTINCT run_name), MAX(created_at) FROM configs",
+    list_runs: "SELECT run_name, created_at FROM configs ORDER BY created_at DESC",
+    run_configs: Some("SELECT run_name, config FROM configs ORDER BY created_at"),
+};
+
+impl SchemaVersion {
+    /// Detect the schema generation of a database from its tables and columns
+    fn detect(conn: &Connection) -> Result<Self> {
+        let metrics = table_columns(conn, "metrics")?;
+        if metrics.is_empty() {
+            anyhow::bail!("no `metrics` table");
+        }
+        check_columns("metrics", &metrics, METRICS_COLUMNS)?;
+
+        let configs = table_columns(conn, "configs")?;
+        if configs.is_empty() {
+            return Ok(SchemaVersion::MetricsOnly);
+        }
+        check_columns("configs", &configs, CONFIGS_COLUMNS)?;
+        Ok(SchemaVersion::WithConfigs)
+    }
+
+    fn queries(self) -> &'static QuerySet {
+        match self {
+            SchemaVersion::MetricsOnly => &METRICS_ONLY_QUERIES,
+            SchemaVersion::WithConfigs => &WITH_CONFIGS_QUERIES,
+        }
+    }
+}
+
+/// Column names of a table (empty if the table doesn't exist)
+fn table_columns(conn: &Connection, table: &str) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(columns)
+}
+
+fn check_columns(table: &str, columns: &HashSet<String>, required: &[&str]) -> Result<()> {
+    let missing: Vec<&str> = required
+        .iter()
+        .filter(|c| !columns.contains(**c))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!("`{table}` table has no {} column", missing.join("/"));
+    }
+    Ok(())
+}
+
+/// An open project database and the queries matching its schema
+struct ProjectDb {
+    conn: Connection,
+    queries: &'static QuerySet,
+}
+
+impl ProjectDb {
+    /// Open a database read-only and detect its schema
+    fn open(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            anyhow::bail!("Project database not found: {path:?}");
+        }
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Failed to open database: {path:?}"))?;
+        let schema = SchemaVersion::detect(&conn)
+            .with_context(|| format!("Unsupported trackio schema in {path:?}"))?;
+        Ok(ProjectDb {
+            conn,
+            queries: schema.queries(),
+        })
+    }
+}
+
 /// Storage interface for trackio's SQLite database.
 /// Caches database connections per project for efficiency.
 pub struct Storage {
     db_path: PathBuf,
     /// Cached connections per project (interior mutability for caching)
-    connections: RefCell<HashMap<String, Connection>>,
+    connections: RefCell<HashMap<String, ProjectDb>>,
+    /// Incremental load cursors keyed by (project, run)
+    cursors: RefCell<HashMap<(String, String), MetricsCursor>>,
+    /// Incremental load cursors for non-scalar values, keyed by (project, run)
+    rich_cursors: RefCell<HashMap<(String, String), MetricsCursor>>,
 }
 
 impl Storage {
@@ -59,6 +287,8 @@ impl Storage {
         Storage {
             db_path,
             connections: RefCell::new(HashMap::new()),
+            cursors: RefCell::new(HashMap::new()),
+            rich_cursors: RefCell::new(HashMap::new()),
         }
     }
 
@@ -67,43 +297,41 @@ impl Storage {
         self.db_path.join(format!("{project}.db"))
     }
 
-    /// Open a read-only connection to a project database (for one-off queries).
-    /// For frequently accessed projects, connections are cached automatically.
-    fn open_project_db(&self, project: &str) -> Result<Connection> {
-        let path = self.project_db_path(project);
-        if !path.exists() {
-            anyhow::bail!("Project database not found: {path:?}");
-        }
-        Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
-            .with_context(|| format!("Failed to open database: {path:?}"))
-    }
-
-    /// Execute a function with a cached connection to a project database.
+    /// Execute a function with a cached connection to a project database,
+    /// along with the queries for its schema.
     /// The connection is cached for subsequent calls to the same project.
     fn with_connection<T, F>(&self, project: &str, f: F) -> Result<T>
     where
-        F: FnOnce(&Connection) -> Result<T>,
+        F: FnOnce(&Connection, &QuerySet) -> Result<T>,
     {
         let mut connections = self.connections.borrow_mut();
 
         // Ensure connection exists in cache
         if !connections.contains_key(project) {
-            let path = self.project_db_path(project);
-            if !path.exists() {
-                anyhow::bail!("Project database not found: {path:?}");
-            }
-            let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
-                .with_context(|| format!("Failed to open database: {path:?}"))?;
-            connections.insert(project.to_string(), conn);
+            let db = ProjectDb::open(&self.project_db_path(project))?;
+            connections.insert(project.to_string(), db);
         }
 
         // Get reference and execute function
-        let conn = connections.get(project).unwrap();
-        f(conn)
+        let db = connections.get(project).unwrap();
+        f(&db.conn, db.queries)
     }
 
+    /// Get statistics for a project (run count, last updated)
+    fn get_project_stats(&self, project: &str) -> Result<(usize, Option<DateTime<Utc>>)> {
+        // One-off connection: listing projects shouldn't keep every database open
+        let db = ProjectDb::open(&self.project_db_path(project))?;
+        let (run_count, last_updated): (usize, Option<String>) =
+            db.conn.query_row(db.queries.project_stats, [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?;
+        Ok((run_count, parse_timestamp(last_updated)))
+    }
+}
+
+impl StorageBackend for Storage {
     /// List all available projects by scanning for .db files
-    pub fn list_projects(&self) -> Result<Vec<Project>> {
+    fn list_projects(&self) -> Result<Vec<Project>> {
         let mut projects = Vec::new();
 
         if !self.db_path.exists() {
@@ -122,127 +350,562 @@ impl Storage {
                         continue;
                     }
 
-                    // Try to get run count and last updated from the database
+                    // Still list projects whose stats can't be read (e.g. unsupported
+                    // schema), so selecting them shows why their runs are missing
                     let (run_count, last_updated) =
                         self.get_project_stats(name).unwrap_or((0, None));
 
-                    projects.push(Project {
-                        name: name.to_string(),
-                        run_count,
-                        last_updated,
-                    });
+                    projects.push(Project::new(name.to_string(), run_count, last_updated));
                 }
             }
         }
 
         // Sort by last updated (most recent first)
-        projects.sort_by(|a, b| b.last_updated.cmp(&a.last_updated));
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_updated));
 
         Ok(projects)
     }
 
-    /// Get statistics for a project (run count, last updated)
-    fn get_project_stats(&self, project: &str) -> Result<(usize, Option<DateTime<Utc>>)> {
-        let conn = self.open_project_db(project)?;
-
-        // Get distinct run count from configs table
-        let run_count: usize = conn
-            .query_row("SELECT COUNT(DISTINCT run_name) FROM configs", [], |row| {
-                row.get(0)
-            })
-            .unwrap_or(0);
-
-        // Get last updated timestamp from configs
-        let last_updated: Option<String> = conn
-            .query_row("SELECT MAX(created_at) FROM configs", [], |row| row.get(0))
-            .ok();
-
-        let last_updated = parse_timestamp(last_updated);
-
-        Ok((run_count, last_updated))
-    }
-
     /// List all runs for a project (uses cached connection)
-    pub fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
         let project_str = project.to_string();
-        self.with_connection(project, |conn| {
-            let mut runs = Vec::new();
+        self.with_connection(project, |conn, queries| {
+            // Latest config of each run, by run name
+            let mut configs: HashMap<String, Vec<Config>> = HashMap::new();
+            if let Some(run_configs) = queries.run_configs {
+                let mut stmt = conn.prepare(run_configs)?;
+                let config_iter = stmt.query_map([], |row| {
+                    // Config can be stored as TEXT or BLOB depending on how trackio wrote it
+                    Ok((row.get::<_, String>(0)?, get_string_or_blob(row, 1)?))
+                })?;
+                for config_result in config_iter {
+                    let (run_name, config_json) = config_result?;
+                    configs.insert(
+                        run_name,
+                        parse_config_json(&config_json).unwrap_or_default(),
+                    );
+                }
+            }
 
-            // Query configs table for run information
+            // Runs whose latest row logs a non-finite training value
+            let mut diverged: HashSet<String> = HashSet::new();
             let mut stmt = conn.prepare(
-                "SELECT run_name, config, created_at FROM configs ORDER BY created_at DESC",
+                "SELECT run_name, metrics FROM metrics \
+                 WHERE id IN (SELECT MAX(id) FROM metrics GROUP BY run_name)",
             )?;
+            let latest_rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, get_string_or_blob(row, 1)?))
+            })?;
+            for latest_row in latest_rows {
+                let (run_name, metrics_json) = latest_row?;
+                let has_non_finite = parse_metrics_json(&metrics_json).is_some_and(|map| {
+                    map.iter().any(|(name, value)| {
+                        !name.starts_with(SYSTEM_METRIC_PREFIX)
+                            && metric_value(value).is_some_and(|v| !v.is_finite())
+                    })
+                });
+                if has_non_finite {
+                    diverged.insert(run_name);
+                }
+            }
 
+            let mut runs = Vec::new();
+            let mut stmt = conn.prepare(queries.list_runs)?;
             let run_iter = stmt.query_map([], |row| {
                 let run_name: String = row.get(0)?;
-                // Config can be stored as TEXT or BLOB depending on how trackio wrote it
-                let config_json: String = get_string_or_blob(row, 1)?;
-                let created_at: String = row.get(2)?;
-                Ok((run_name, config_json, created_at))
+                let created_at: Option<String> = row.get(1)?;
+                Ok((run_name, created_at))
             })?;
 
             for run_result in run_iter {
-                let (run_name, config_json, created_at) = run_result?;
-
-                let config = parse_config_json(&config_json).unwrap_or_default();
-
-                let created_at = parse_timestamp(Some(created_at));
-
-                runs.push(Run::new(run_name, project_str.clone(), created_at, config));
+                let (run_name, created_at) = run_result?;
+                let config = configs.get(&run_name).cloned().unwrap_or_default();
+                let created_at = parse_timestamp(created_at);
+                let mut run = Run::new(run_name, project_str.clone(), created_at, config);
+                run.diverged = diverged.contains(&run.id);
+                runs.push(run);
             }
 
             Ok(runs)
         })
     }
 
-    /// Get all metrics for a run (single pass through data, uses cached connection)
-    pub fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+    /// Get all metrics for a run (single pass through data, uses cached connection).
+    /// Also resets the incremental cursor for the run, so subsequent calls to
+    /// `get_new_metrics` only return rows logged after this load.
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
         let run_id_str = run_id.to_string();
-        self.with_connection(project, |conn| {
+        let (metrics, cursor) = self.with_connection(project, |conn, _| {
             let mut stmt = conn.prepare(
-                "SELECT step, metrics, timestamp FROM metrics WHERE run_name = ? ORDER BY step",
+                "SELECT id, step, metrics, timestamp FROM metrics WHERE run_name = ? ORDER BY step",
             )?;
+            let rows = stmt.query_map([&run_id_str], read_metrics_row)?;
+            collect_metrics(rows, MetricsCursor::default())
+        })?;
 
-            let mut metrics_map: HashMap<String, Metric> = HashMap::new();
+        self.cursors
+            .borrow_mut()
+            .insert((project.to_string(), run_id.to_string()), cursor);
 
-            let row_iter = stmt.query_map([&run_id_str], |row| {
-                let step: i64 = row.get(0)?;
-                let metrics_json: String = get_string_or_blob(row, 1)?;
+        Ok(metrics)
+    }
+
+    /// Get system metrics from the `system_metrics` table, if the database has one.
+    /// System samples have no training step, so their sample index is used instead.
+    fn get_system_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        let run_id_str = run_id.to_string();
+        self.with_connection(project, |conn, _| {
+            let columns = table_columns(conn, "system_metrics")?;
+            if SYSTEM_METRICS_COLUMNS.iter().any(|c| !columns.contains(*c)) {
+                return Ok(Vec::new());
+            }
+
+            let mut stmt = conn.prepare(
+                "SELECT id, metrics, timestamp FROM system_metrics WHERE run_name = ? ORDER BY id",
+            )?;
+            let mut index = 0;
+            let rows = stmt.query_map([&run_id_str], |row| {
+                let id: i64 = row.get(0)?;
+                let metrics_json = get_string_or_blob(row, 1)?;
                 let timestamp: Option<String> = row.get(2)?;
-                Ok((step, metrics_json, timestamp))
+                index += 1;
+                Ok((id, index - 1, metrics_json, timestamp))
             })?;
+            let (mut metrics, _) = collect_metrics(rows, MetricsCursor::default())?;
 
-            for row in row_iter {
-                let (step, metrics_json, timestamp) = row?;
-                let ts = parse_timestamp(timestamp);
-
-                if let Ok(map) =
-                    serde_json::from_str::<HashMap<String, serde_json::Value>>(&metrics_json)
-                {
-                    for (name, value) in map {
-                        if let Some(v) = value.as_f64() {
-                            let metric = metrics_map
-                                .entry(name.clone())
-                                .or_insert_with(|| Metric::new(name));
-                            metric.points.push(MetricPoint {
-                                step,
-                                value: v,
-                                timestamp: ts,
-                            });
-                        }
-                    }
+            for metric in &mut metrics {
+                if !metric.is_system() {
+                    metric.name = format!("{SYSTEM_METRIC_PREFIX}{}", metric.name);
                 }
             }
-
-            let mut metrics: Vec<Metric> = metrics_map.into_values().collect();
             metrics.sort_by(|a, b| a.name.cmp(&b.name));
             Ok(metrics)
         })
     }
+
+    /// Get the histograms, tables and media logged for a run.
+    /// Only rows whose JSON could hold such values are parsed.
+    /// Also resets the run's rich values cursor for `get_new_rich_values`.
+    fn get_rich_values(&self, project: &str, run_id: &str) -> Result<RichValues> {
+        let run_id_str = run_id.to_string();
+        let (rich_values, cursor) = self.with_connection(project, |conn, _| {
+            // Read up to the rows counted by the cursor, so later rows are left for the next load
+            let cursor = advance_cursor(conn, &run_id_str, MetricsCursor::default())?;
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, step, metrics, timestamp FROM metrics \
+                 WHERE run_name = ? AND id <= ? AND {RICH_VALUES_FILTER} ORDER BY step"
+            ))?;
+            let rows = stmt.query_map(
+                rusqlite::params![&run_id_str, cursor.last_id],
+                read_metrics_row,
+            )?;
+            Ok((collect_rich_values(rows, &self.db_path)?, cursor))
+        })?;
+
+        self.rich_cursors
+            .borrow_mut()
+            .insert((project.to_string(), run_id.to_string()), cursor);
+
+        Ok(rich_values)
+    }
+
+    /// Get non-scalar values logged since the last load of this run.
+    /// Like `get_new_metrics`, falls back to a full reload when the run was never
+    /// loaded or rows have disappeared.
+    fn get_new_rich_values(&self, project: &str, run_id: &str) -> Result<RichValuesUpdate> {
+        let key = (project.to_string(), run_id.to_string());
+        let Some(cursor) = self.rich_cursors.borrow().get(&key).copied() else {
+            return Ok(RichValuesUpdate::Reload(
+                self.get_rich_values(project, run_id)?,
+            ));
+        };
+
+        let run_id_str = run_id.to_string();
+        let appended = self.with_connection(project, |conn, _| {
+            if !rows_unchanged(conn, &run_id_str, cursor)? {
+                return Ok(None);
+            }
+            let new_cursor = advance_cursor(conn, &run_id_str, cursor)?;
+            if new_cursor.row_count == cursor.row_count {
+                return Ok(Some((RichValues::default(), new_cursor)));
+            }
+
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, step, metrics, timestamp FROM metrics \
+                 WHERE run_name = ? AND id > ? AND id <= ? AND {RICH_VALUES_FILTER} ORDER BY id"
+            ))?;
+            let rows = stmt.query_map(
+                rusqlite::params![&run_id_str, cursor.last_id, new_cursor.last_id],
+                read_metrics_row,
+            )?;
+            Ok(Some((
+                collect_rich_values(rows, &self.db_path)?,
+                new_cursor,
+            )))
+        })?;
+
+        match appended {
+            Some((rich_values, cursor)) => {
+                self.rich_cursors.borrow_mut().insert(key, cursor);
+                Ok(RichValuesUpdate::Append(rich_values))
+            }
+            None => Ok(RichValuesUpdate::Reload(
+                self.get_rich_values(project, run_id)?,
+            )),
+        }
+    }
+
+    /// Get metrics logged since the last load of this run.
+    ///
+    /// Returns `MetricsUpdate::Append` with only the new points when previously
+    /// seen rows are unchanged, and falls back to a full `MetricsUpdate::Reload`
+    /// when the run was never loaded or rows have disappeared (e.g. run deleted
+    /// and re-logged).
+    fn get_new_metrics(&self, project: &str, run_id: &str) -> Result<MetricsUpdate> {
+        let key = (project.to_string(), run_id.to_string());
+        let Some(cursor) = self.cursors.borrow().get(&key).copied() else {
+            return Ok(MetricsUpdate::Reload(
+                self.get_all_metrics(project, run_id)?,
+            ));
+        };
+
+        let run_id_str = run_id.to_string();
+        let appended = self.with_connection(project, |conn, _| {
+            if !rows_unchanged(conn, &run_id_str, cursor)? {
+                return Ok(None);
+            }
+
+            let mut stmt = conn.prepare(
+                "SELECT id, step, metrics, timestamp FROM metrics \
+                 WHERE run_name = ? AND id > ? ORDER BY id",
+            )?;
+            let rows = stmt.query_map(
+                rusqlite::params![&run_id_str, cursor.last_id],
+                read_metrics_row,
+            )?;
+            collect_metrics(rows, cursor).map(Some)
+        })?;
+
+        match appended {
+            Some((metrics, cursor)) => {
+                self.cursors.borrow_mut().insert(key, cursor);
+                Ok(MetricsUpdate::Append(metrics))
+            }
+            None => Ok(MetricsUpdate::Reload(
+                self.get_all_metrics(project, run_id)?,
+            )),
+        }
+    }
+}
+
+/// Whether the rows of a run already seen by `cursor` are all still there
+fn rows_unchanged(conn: &Connection, run_id: &str, cursor: MetricsCursor) -> Result<bool> {
+    let seen_rows: usize = conn.query_row(
+        "SELECT COUNT(*) FROM metrics WHERE run_name = ? AND id <= ?",
+        rusqlite::params![run_id, cursor.last_id],
+        |row| row.get(0),
+    )?;
+    Ok(seen_rows == cursor.row_count)
+}
+
+/// Move `cursor` past every row of a run logged after it
+fn advance_cursor(conn: &Connection, run_id: &str, cursor: MetricsCursor) -> Result<MetricsCursor> {
+    let (last_id, new_rows): (Option<i64>, usize) = conn.query_row(
+        "SELECT MAX(id), COUNT(*) FROM metrics WHERE run_name = ? AND id > ?",
+        rusqlite::params![run_id, cursor.last_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    Ok(MetricsCursor {
+        last_id: last_id.unwrap_or(cursor.last_id),
+        row_count: cursor.row_count + new_rows,
+    })
+}
+
+/// A raw row from the `metrics` table: (id, step, metrics JSON, timestamp)
+type MetricsRow = (i64, i64, String, Option<String>);
+
+/// Read a `SELECT id, step, metrics, timestamp` row
+fn read_metrics_row(row: &Row) -> rusqlite::Result<MetricsRow> {
+    let id: i64 = row.get(0)?;
+    let step: i64 = row.get(1)?;
+    let metrics_json: String = get_string_or_blob(row, 2)?;
+    let timestamp: Option<String> = row.get(3)?;
+    Ok((id, step, metrics_json, timestamp))
+}
+
+/// Group metrics rows into per-name series, advancing `cursor` past every row read.
+/// Returned metrics are sorted by name; points keep the row order of the query.
+fn collect_metrics<I>(rows: I, mut cursor: MetricsCursor) -> Result<(Vec<Metric>, MetricsCursor)>
+where
+    I: Iterator<Item = rusqlite::Result<MetricsRow>>,
+{
+    let mut metrics_map: HashMap<String, Metric> = HashMap::new();
+
+    for row in rows {
+        let (id, step, metrics_json, timestamp) = row?;
+        cursor.last_id = cursor.last_id.max(id);
+        cursor.row_count += 1;
+
+        let ts = parse_timestamp(timestamp);
+
+        if let Some(map) = parse_metrics_json(&metrics_json) {
+            for (name, value) in map {
+                if let Some(v) = metric_value(&value) {
+                    let metric = metrics_map
+                        .entry(name.clone())
+                        .or_insert_with(|| Metric::new(name));
+                    metric.points_mut().push(MetricPoint {
+                        step,
+                        value: v,
+                        timestamp: ts,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut metrics: Vec<Metric> = metrics_map.into_values().collect();
+    metrics.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok((metrics, cursor))
+}
+
+/// Group the non-scalar values of metrics rows into per-name series sorted by name.
+/// Media paths are resolved against the data root `db_path`.
+fn collect_rich_values<I>(rows: I, db_path: &Path) -> Result<RichValues>
+where
+    I: Iterator<Item = rusqlite::Result<MetricsRow>>,
+{
+    let mut histograms: HashMap<String, Histogram> = HashMap::new();
+    let mut tables: HashMap<String, Table> = HashMap::new();
+    let mut media: HashMap<String, Media> = HashMap::new();
+    let mut texts: HashMap<String, TextSeries> = HashMap::new();
+
+    for row in rows {
+        let (_, step, metrics_json, timestamp) = row?;
+        let Some(map) = parse_metrics_json(&metrics_json) else {
+            continue;
+        };
+        let ts = parse_timestamp(timestamp);
+
+        for (name, value) in map {
+            if metric_value(&value).is_some() {
+                // A scalar (possibly non-finite), loaded with the metrics
+                continue;
+            }
+            if let serde_json::Value::String(text) = value {
+                let series = texts.entry(name.clone()).or_insert_with(|| TextSeries {
+                    name,
+                    points: Vec::new(),
+                });
+                series.points.push(TextPoint {
+                    step,
+                    timestamp: ts,
+                    text,
+                });
+            } else if let Some((edges, counts)) = parse_histogram(&value) {
+                let histogram = histograms.entry(name.clone()).or_insert_with(|| Histogram {
+                    name,
+                    points: Vec::new(),
+                });
+                histogram.points.push(HistogramPoint {
+                    step,
+                    timestamp: ts,
+                    edges,
+                    counts,
+                });
+            } else if let Some((columns, rows)) = parse_table(&value) {
+                let table = tables.entry(name.clone()).or_insert_with(|| Table {
+                    name,
+                    points: Vec::new(),
+                });
+                table.points.push(TablePoint {
+                    step,
+                    timestamp: ts,
+                    columns,
+                    rows,
+                });
+            } else {
+                // A single file or a list of files logged under one name
+                let files = match &value {
+                    serde_json::Value::Array(items) => items.iter().collect(),
+                    _ => vec![&value],
+                };
+                for (kind, file, caption) in files.into_iter().filter_map(parse_media) {
+                    let series = media.entry(name.clone()).or_insert_with(|| Media {
+                        name: name.clone(),
+                        points: Vec::new(),
+                    });
+                    series.points.push(MediaPoint {
+                        step,
+                        timestamp: ts,
+                        kind,
+                        path: resolve_media_path(db_path, &file),
+                        caption,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut histograms: Vec<Histogram> = histograms.into_values().collect();
+    histograms.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut tables: Vec<Table> = tables.into_values().collect();
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut media: Vec<Media> = media.into_values().collect();
+    media.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut texts: Vec<TextSeries> = texts.into_values().collect();
+    texts.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(RichValues {
+        histograms,
+        tables,
+        media,
+        texts,
+    })
+}
+
+/// Parse a media value (`{"_type": "trackio.image", "file_path": ..., "caption": ...}`).
+/// Returns (kind, file path as logged, caption).
+pub(super) fn parse_media(value: &serde_json::Value) -> Option<(String, String, Option<String>)> {
+    let obj = value.as_object()?;
+    let media_type = obj.get("_type")?.as_str()?;
+    let file = obj.get("file_path")?.as_str()?;
+    let kind = media_type.rsplit('.').next().unwrap_or(media_type);
+    let caption = obj
+        .get("caption")
+        .and_then(serde_json::Value::as_str)
+        .filter(|c| !c.is_empty())
+        .map(str::to_string);
+    Some((kind.to_string(), file.to_string(), caption))
+}
+
+/// Locate a logged media file. trackio records paths relative to the `media/`
+/// directory of the data root; absolute paths are used as they are.
+fn resolve_media_path(db_path: &Path, file: &str) -> PathBuf {
+    let media_path = db_path.join("media").join(file);
+    if Path::new(file).is_absolute() || media_path.exists() {
+        return media_path;
+    }
+    let root_path = db_path.join(file);
+    if root_path.exists() {
+        root_path
+    } else {
+        media_path
+    }
+}
+
+/// Parse a histogram value: an object with `bins` and `values` (or `counts`).
+/// `bins` may hold the bin edges (one more than the counts) or the bin centers,
+/// in which case edges are placed halfway between neighbouring centers.
+/// Returns (edges, counts).
+pub(super) fn parse_histogram(value: &serde_json::Value) -> Option<(Vec<f64>, Vec<f64>)> {
+    let obj = value.as_object()?;
+    let numbers = |key: &str| -> Option<Vec<f64>> {
+        obj.get(key)?
+            .as_array()?
+            .iter()
+            .map(serde_json::Value::as_f64)
+            .collect()
+    };
+    let bins = numbers("bins")?;
+    let counts = numbers("values").or_else(|| numbers("counts"))?;
+    if counts.is_empty() {
+        return None;
+    }
+
+    let edges = if bins.len() == counts.len() + 1 {
+        bins
+    } else if bins.len() == counts.len() {
+        let half_width = |i: usize, j: usize| (bins[j] - bins[i]) / 2.0;
+        let first_half = if bins.len() > 1 {
+            half_width(0, 1)
+        } else {
+            0.5
+        };
+        let mut edges = vec![bins[0] - first_half];
+        edges.extend(bins.windows(2).map(|w| (w[0] + w[1]) / 2.0));
+        let last = bins.len() - 1;
+        let last_half = if last > 0 {
+            half_width(last - 1, last)
+        } else {
+            0.5
+        };
+        edges.push(bins[last] + last_half);
+        edges
+    } else {
+        return None;
+    };
+
+    if edges.iter().any(|e| !e.is_finite()) || edges.windows(2).any(|w| w[1] < w[0]) {
+        return None;
+    }
+    Some((edges, counts))
+}
+
+/// Parse a table value. Accepts trackio tables (`{"_type": "trackio.table",
+/// "_value": [records]}`) and split layouts (`{"columns": [...], "data": [[...]]}`,
+/// optionally nested under `_value`). Returns (columns, rows).
+pub(super) fn parse_table(
+    value: &serde_json::Value,
+) -> Option<(Vec<String>, Vec<Vec<ConfigValue>>)> {
+    let obj = value.as_object()?;
+    let is_table_type = obj
+        .get("_type")
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|t| t.ends_with("table"));
+    let inner = obj.get("_value").unwrap_or(value);
+
+    // Split layout: column names plus rows of cells
+    if let Some(split) = inner.as_object() {
+        let columns: Vec<String> = split
+            .get("columns")?
+            .as_array()?
+            .iter()
+            .map(|c| {
+                c.as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| c.to_string())
+            })
+            .collect();
+        let rows = split
+            .get("data")?
+            .as_array()?
+            .iter()
+            .map(|row| {
+                let cells = row.as_array()?;
+                Some(cells.iter().cloned().map(json_to_config_value).collect())
+            })
+            .collect::<Option<Vec<Vec<ConfigValue>>>>()?;
+        return Some((columns, rows));
+    }
+
+    // Records layout: one object per row; columns in order of first appearance
+    if !is_table_type {
+        return None;
+    }
+    let records = inner.as_array()?;
+    let mut columns: Vec<String> = Vec::new();
+    for record in records {
+        for key in record.as_object()?.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    let rows = records
+        .iter()
+        .filter_map(serde_json::Value::as_object)
+        .map(|record| {
+            columns
+                .iter()
+                .map(|c| json_to_config_value(record.get(c).cloned().unwrap_or_default()))
+                .collect()
+        })
+        .collect();
+    Some((columns, rows))
 }
 
 /// Parse JSON config string into Config vector
-fn parse_config_json(json: &str) -> Result<Vec<Config>> {
+pub(super) fn parse_config_json(json: &str) -> Result<Vec<Config>> {
     let map: HashMap<String, serde_json::Value> = serde_json::from_str(json)?;
 
     let mut configs: Vec<Config> = map
@@ -261,7 +924,7 @@ fn parse_config_json(json: &str) -> Result<Vec<Config>> {
 }
 
 /// Convert JSON value to ConfigValue
-fn json_to_config_value(value: serde_json::Value) -> ConfigValue {
+pub(super) fn json_to_config_value(value: serde_json::Value) -> ConfigValue {
     match value {
         serde_json::Value::Null => ConfigValue::Null,
         serde_json::Value::Bool(b) => ConfigValue::Bool(b),
@@ -288,6 +951,508 @@ fn json_to_config_value(value: serde_json::Value) -> ConfigValue {
 mod tests {
     use super::*;
 
+    /// Create a project database with trackio's schema in `dir`
+    fn create_test_db(dir: &std::path::Path, project: &str) -> Connection {
+        let conn = Connection::open(dir.join(format!("{project}.db"))).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE metrics (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                run_name TEXT NOT NULL,
+                step INTEGER NOT NULL,
+                metrics TEXT NOT NULL
+            );
+            CREATE TABLE configs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_name TEXT NOT NULL,
+                config TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn log_metrics(conn: &Connection, run: &str, step: i64, metrics: &str) {
+        conn.execute(
+            "INSERT INTO metrics (timestamp, run_name, step, metrics) VALUES (?, ?, ?, ?)",
+            rusqlite::params!["2025-01-01T00:00:00", run, step, metrics],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_get_new_metrics_appends_only_new_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        log_metrics(&conn, "run-a", 0, r#"{"loss": 1.0}"#);
+        log_metrics(&conn, "run-a", 1, r#"{"loss": 0.8}"#);
+        log_metrics(&conn, "run-b", 0, r#"{"loss": 2.0}"#);
+
+        let storage = Storage::new(dir.path().to_path_buf());
+        let mut metrics = storage.get_all_metrics("proj", "run-a").unwrap();
+        assert_eq!(metrics[0].points().len(), 2);
+
+        // Nothing new yet
+        let update = storage.get_new_metrics("proj", "run-a").unwrap();
+        assert!(update.is_empty());
+
+        log_metrics(&conn, "run-a", 2, r#"{"loss": 0.6, "accuracy": 0.5}"#);
+        log_metrics(&conn, "run-b", 1, r#"{"loss": 1.5}"#);
+
+        let update = storage.get_new_metrics("proj", "run-a").unwrap();
+        let MetricsUpdate::Append(ref new_metrics) = update else {
+            panic!("expected an append, got {update:?}");
+        };
+        assert_eq!(new_metrics.len(), 2);
+        update.apply(&mut metrics);
+
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["accuracy", "loss"]);
+        let steps: Vec<i64> = metrics[1].points().iter().map(|p| p.step).collect();
+        assert_eq!(steps, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_get_new_metrics_reloads_when_rows_disappear() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        log_metrics(&conn, "run-a", 0, r#"{"loss": 1.0}"#);
+        log_metrics(&conn, "run-a", 1, r#"{"loss": 0.8}"#);
+
+        let storage = Storage::new(dir.path().to_path_buf());
+        storage.get_all_metrics("proj", "run-a").unwrap();
+
+        conn.execute("DELETE FROM metrics WHERE step = 0", [])
+            .unwrap();
+
+        match storage.get_new_metrics("proj", "run-a").unwrap() {
+            MetricsUpdate::Reload(metrics) => assert_eq!(metrics[0].points().len(), 1),
+            other => panic!("expected a reload, got {other:?}"),
+        }
+    }
+
+    /// Create a project database from older trackio releases, without a `configs` table
+    fn create_metrics_only_db(dir: &std::path::Path, project: &str) -> Connection {
+        let conn = Connection::open(dir.join(format!("{project}.db"))).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE metrics (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                run_name TEXT NOT NULL,
+                step INTEGER NOT NULL,
+                metrics TEXT NOT NULL
+            );",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_metrics_only_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_metrics_only_db(dir.path(), "old");
+        log_metrics(&conn, "run-a", 0, r#"{"loss": 1.0}"#);
+        log_metrics(&conn, "run-a", 1, r#"{"loss": 0.5}"#);
+        log_metrics(&conn, "run-b", 0, r#"{"loss": 2.0}"#);
+
+        let storage = Storage::new(dir.path().to_path_buf());
+
+        let projects = storage.list_projects().unwrap();
+        assert_eq!(projects[0].run_count, 2);
+        assert!(projects[0].last_updated.is_some());
+
+        let runs = storage.list_runs("old").unwrap();
+        let mut ids: Vec<&str> = runs.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["run-a", "run-b"]);
+        assert!(runs.iter().all(|r| r.config.is_empty()));
+
+        assert_eq!(
+            storage.get_all_metrics("old", "run-a").unwrap()[0]
+                .points()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_unsupported_schema_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = Connection::open(dir.path().join("future.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE metrics (id INTEGER PRIMARY KEY, run_id TEXT, payload TEXT);",
+        )
+        .unwrap();
+        Connection::open(dir.path().join("empty.db"))
+            .unwrap()
+            .execute_batch("CREATE TABLE other (x INTEGER);")
+            .unwrap();
+
+        let storage = Storage::new(dir.path().to_path_buf());
+
+        // Still listed, so selecting them explains the problem
+        assert_eq!(storage.list_projects().unwrap().len(), 2);
+
+        let err = format!("{:#}", storage.list_runs("future").unwrap_err());
+        assert!(err.contains("Unsupported trackio schema"), "{err}");
+        assert!(err.contains("`metrics` table has no"), "{err}");
+
+        let err = format!("{:#}", storage.get_all_metrics("empty", "run").unwrap_err());
+        assert!(err.contains("no `metrics` table"), "{err}");
+    }
+
+    #[test]
+    fn test_get_system_metrics() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        let storage = Storage::new(dir.path().to_path_buf());
+
+        // No system_metrics table
+        assert!(storage
+            .get_system_metrics("proj", "run-a")
+            .unwrap()
+            .is_empty());
+
+        conn.execute_batch(
+            "CREATE TABLE system_metrics (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                run_name TEXT NOT NULL,
+                metrics TEXT NOT NULL
+            );
+            INSERT INTO system_metrics (timestamp, run_name, metrics) VALUES
+                ('2025-01-01T00:00:00', 'run-a', '{\"cpu\": 10.0, \"system/gpu\": 1.0}'),
+                ('2025-01-01T00:00:10', 'run-a', '{\"cpu\": 20.0}'),
+                ('2025-01-01T00:00:10', 'run-b', '{\"cpu\": 99.0}');",
+        )
+        .unwrap();
+
+        let metrics = storage.get_system_metrics("proj", "run-a").unwrap();
+        let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["system/cpu", "system/gpu"]);
+        // Samples are numbered in logging order
+        let steps: Vec<i64> = metrics[0].points().iter().map(|p| p.step).collect();
+        assert_eq!(steps, vec![0, 1]);
+        assert_eq!(metrics[0].points()[1].value, 20.0);
+    }
+
+    #[test]
+    fn test_get_rich_values_histograms() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        log_metrics(
+            &conn,
+            "run-a",
+            0,
+            r#"{"loss": 1.0, "weights": {"_type": "trackio.histogram", "bins": [0, 1, 2], "values": [3, 4]}}"#,
+        );
+        log_metrics(
+            &conn,
+            "run-a",
+            1,
+            r#"{"weights": {"_type": "trackio.histogram", "bins": [0, 1, 2], "values": [1, 6]}}"#,
+        );
+        log_metrics(
+            &conn,
+            "run-a",
+            2,
+            r#"{"samples": {"_type": "trackio.table", "_value": [{"text": "hi"}]}}"#,
+        );
+        log_metrics(
+            &conn,
+            "run-a",
+            3,
+            r#"{"image": {"_type": "trackio.image", "file_path": "proj/run-a/3/cat.png", "caption": "a cat"}}"#,
+        );
+        log_metrics(&conn, "run-b", 0, r#"{"loss": 2.0}"#);
+        let storage = Storage::new(dir.path().to_path_buf());
+
+        let rich = storage.get_rich_values("proj", "run-a").unwrap();
+        assert_eq!(
+            rich.names().collect::<Vec<_>>(),
+            vec!["weights", "samples", "image"]
+        );
+        assert_eq!(rich.table("samples").unwrap().points[0].step, 2);
+        let sample = &rich.media("image").unwrap().points[0];
+        assert_eq!(sample.kind, "image");
+        assert_eq!(sample.caption.as_deref(), Some("a cat"));
+        assert_eq!(
+            sample.path,
+            dir.path().join("media").join("proj/run-a/3/cat.png")
+        );
+        let weights = rich.histogram("weights").unwrap();
+        assert_eq!(weights.points.len(), 2);
+        assert_eq!(weights.points[1].step, 1);
+        assert_eq!(weights.points[1].counts, vec![1.0, 6.0]);
+
+        // Histograms stay out of the scalar metrics
+        let metrics = storage.get_all_metrics("proj", "run-a").unwrap();
+        assert_eq!(metrics.len(), 1);
+        assert!(storage
+            .get_rich_values("proj", "run-b")
+            .unwrap()
+            .histograms
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_rich_values_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        log_metrics(
+            &conn,
+            "run-a",
+            0,
+            r#"{"loss": 1.0, "status": "warming up"}"#,
+        );
+        log_metrics(
+            &conn,
+            "run-a",
+            1,
+            r#"{"sample": "The cat sat", "status":"training"}"#,
+        );
+        let storage = Storage::new(dir.path().to_path_buf());
+
+        let rich = storage.get_rich_values("proj", "run-a").unwrap();
+        // Text stays out of the charted series
+        assert_eq!(rich.names().count(), 0);
+        let entries: Vec<(&str, i64, &str)> = rich
+            .text_entries()
+            .into_iter()
+            .map(|(name, p)| (name, p.step, p.text.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("status", 0, "warming up"),
+                ("sample", 1, "The cat sat"),
+                ("status", 1, "training"),
+            ]
+        );
+        assert_eq!(storage.get_all_metrics("proj", "run-a").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_get_new_rich_values_appends_new_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        log_metrics(
+            &conn,
+            "run-a",
+            0,
+            r#"{"loss": 1.0, "status": "warming up"}"#,
+        );
+        // `_` in the filter is matched literally
+        log_metrics(&conn, "run-a", 1, r#"{"xtype": 1.0}"#);
+        let filtered: usize = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM metrics WHERE {RICH_VALUES_FILTER}"),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(filtered, 1);
+        let storage = Storage::new(dir.path().to_path_buf());
+        let mut rich = storage.get_rich_values("proj", "run-a").unwrap();
+
+        log_metrics(&conn, "run-a", 2, r#"{"loss": 0.5, "status": "training"}"#);
+        let update = storage.get_new_rich_values("proj", "run-a").unwrap();
+        let RichValuesUpdate::Append(ref new_values) = update else {
+            panic!("expected an append, got {update:?}");
+        };
+        // Only the new row's text is sent
+        assert_eq!(new_values.texts[0].points.len(), 1);
+        update.apply(&mut rich);
+        let texts: Vec<&str> = rich
+            .text_entries()
+            .into_iter()
+            .map(|(_, p)| p.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["warming up", "training"]);
+    }
+
+    #[test]
+    fn test_get_new_rich_values_appends_only_new_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        let histogram = r#"{"weights": {"bins": [0, 1, 2], "values": [3, 4]}}"#;
+        log_metrics(&conn, "run-a", 0, histogram);
+        let storage = Storage::new(dir.path().to_path_buf());
+
+        // Without a prior load, everything is loaded
+        let update = storage.get_new_rich_values("proj", "run-a").unwrap();
+        let RichValuesUpdate::Reload(mut rich) = update else {
+            panic!("expected a reload, got {update:?}");
+        };
+        assert_eq!(rich.step_count("weights"), Some(1));
+
+        // Scalar-only rows bring nothing new
+        log_metrics(&conn, "run-a", 1, r#"{"loss": 0.5}"#);
+        assert!(storage
+            .get_new_rich_values("proj", "run-a")
+            .unwrap()
+            .is_empty());
+
+        log_metrics(&conn, "run-a", 2, histogram);
+        log_metrics(&conn, "run-b", 0, histogram);
+        let update = storage.get_new_rich_values("proj", "run-a").unwrap();
+        let RichValuesUpdate::Append(ref new_values) = update else {
+            panic!("expected an append, got {update:?}");
+        };
+        assert_eq!(new_values.histograms[0].points.len(), 1);
+        update.apply(&mut rich);
+        let steps: Vec<i64> = rich.histograms[0].points.iter().map(|p| p.step).collect();
+        assert_eq!(steps, vec![0, 2]);
+
+        conn.execute("DELETE FROM metrics WHERE step = 0", [])
+            .unwrap();
+        assert!(matches!(
+            storage.get_new_rich_values("proj", "run-a").unwrap(),
+            RichValuesUpdate::Reload(rich) if rich.step_count("weights") == Some(1)
+        ));
+    }
+
+    #[test]
+    fn test_parse_table() {
+        let records = serde_json::json!({
+            "_type": "trackio.table",
+            "_value": [{"input": "a", "score": 0.5}, {"input": "b", "score": 2}]
+        });
+        let (columns, rows) = parse_table(&records).unwrap();
+        assert_eq!(columns, vec!["input", "score"]);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][1].to_string(), "2");
+
+        let split = serde_json::json!({"columns": ["x", "y"], "data": [[1, "one"], [2, null]]});
+        let (columns, rows) = parse_table(&split).unwrap();
+        assert_eq!(columns, vec!["x", "y"]);
+        assert!(matches!(rows[1][1], ConfigValue::Null));
+
+        // Plain lists and histograms aren't tables
+        assert!(parse_table(&serde_json::json!([{"a": 1}])).is_none());
+        assert!(parse_table(&serde_json::json!({"bins": [0, 1], "values": [1]})).is_none());
+    }
+
+    #[test]
+    fn test_parse_histogram() {
+        let edges = serde_json::json!({"bins": [0.0, 1.0, 3.0], "values": [5, 6]});
+        assert_eq!(
+            parse_histogram(&edges),
+            Some((vec![0.0, 1.0, 3.0], vec![5.0, 6.0]))
+        );
+
+        // Bin centers become edges halfway between them
+        let centers = serde_json::json!({"bins": [1.0, 3.0], "counts": [5, 6]});
+        assert_eq!(
+            parse_histogram(&centers),
+            Some((vec![0.0, 2.0, 4.0], vec![5.0, 6.0]))
+        );
+
+        let mismatched = serde_json::json!({"bins": [0.0, 1.0, 2.0, 3.0], "values": [1]});
+        assert_eq!(parse_histogram(&mismatched), None);
+        assert_eq!(parse_histogram(&serde_json::json!(1.5)), None);
+        assert_eq!(parse_histogram(&serde_json::json!({"bins": [0, 1]})), None);
+    }
+
+    #[test]
+    fn test_list_runs_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        conn.execute(
+            "INSERT INTO configs (run_name, config, created_at) VALUES (?, ?, ?)",
+            rusqlite::params!["run-a", r#"{"lr": 0.1, "_Created": "x"}"#, "2025-01-01"],
+        )
+        .unwrap();
+
+        let storage = Storage::new(dir.path().to_path_buf());
+        let runs = storage.list_runs("proj").unwrap();
+        let config = &runs.iter().find(|r| r.id == "run-a").unwrap().config;
+        assert_eq!(config.len(), 1);
+        assert_eq!(config[0].key, "lr");
+    }
+
+    #[test]
+    fn test_get_new_metrics_without_prior_load_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        log_metrics(&conn, "run-a", 0, r#"{"loss": 1.0}"#);
+
+        let storage = Storage::new(dir.path().to_path_buf());
+        assert!(matches!(
+            storage.get_new_metrics("proj", "run-a").unwrap(),
+            MetricsUpdate::Reload(_)
+        ));
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        let map = parse_metrics_json(
+            r#"{"loss": NaN, "grad": Infinity, "min": -Infinity, "note": "NaN in \"Infinity\"", "lr": 0.1}"#,
+        )
+        .unwrap();
+        assert!(metric_value(&map["loss"]).unwrap().is_nan());
+        assert_eq!(metric_value(&map["grad"]), Some(f64::INFINITY));
+        assert_eq!(metric_value(&map["min"]), Some(f64::NEG_INFINITY));
+        assert_eq!(metric_value(&map["lr"]), Some(0.1));
+        // Tokens inside strings are left alone
+        assert_eq!(map["note"], "NaN in \"Infinity\"");
+        assert_eq!(metric_value(&map["note"]), None);
+        // Only the bare tokens are numbers, not text that happens to spell them
+        let map = parse_metrics_json(r#"{"status": "NaN", "mode": "Infinity"}"#).unwrap();
+        assert_eq!(map["status"], "NaN");
+        assert_eq!(metric_value(&map["status"]), None);
+        assert_eq!(metric_value(&map["mode"]), None);
+        // Nor text that spells the sentinel itself, however it is escaped
+        let map =
+            parse_metrics_json(r#"{"a": "\u0000NaN", "b": "\u0000N\u0061N", "c": "\\u0000NaN"}"#)
+                .unwrap();
+        assert_eq!(metric_value(&map["a"]), None);
+        assert_eq!(metric_value(&map["b"]), None);
+        assert_eq!(map["c"], "\\u0000NaN");
+
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_test_db(dir.path(), "proj");
+        log_metrics(&conn, "run-a", 0, r#"{"loss": 1.0}"#);
+        log_metrics(&conn, "run-a", 1, r#"{"loss": NaN}"#);
+        let storage = Storage::new(dir.path().to_path_buf());
+        let metrics = storage.get_all_metrics("proj", "run-a").unwrap();
+        assert_eq!(metrics[0].points().len(), 2);
+        assert!(metrics[0].latest_is_non_finite());
+    }
+
+    #[test]
+    fn test_list_runs_flags_diverged() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = create_metrics_only_db(dir.path(), "proj");
+        log_metrics(&conn, "recovered", 0, r#"{"loss": NaN}"#);
+        log_metrics(&conn, "recovered", 1, r#"{"loss": 0.5}"#);
+        log_metrics(&conn, "nan", 0, r#"{"loss": 1.0}"#);
+        log_metrics(&conn, "nan", 1, r#"{"loss": Infinity}"#);
+        log_metrics(&conn, "gpu", 0, r#"{"loss": 1.0, "system/gpu": NaN}"#);
+        let storage = Storage::new(dir.path().to_path_buf());
+
+        let runs = storage.list_runs("proj").unwrap();
+        let diverged: Vec<&str> = runs
+            .iter()
+            .filter(|r| r.diverged)
+            .map(|r| r.id.as_str())
+            .collect();
+        assert_eq!(diverged, vec!["nan"]);
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let naive = parse_timestamp(Some("2025-01-01T12:30:00.123456".to_string())).unwrap();
+        assert_eq!(naive.to_rfc3339(), "2025-01-01T12:30:00.123456+00:00");
+
+        let offset = parse_timestamp(Some("2025-01-01T12:30:00+02:00".to_string())).unwrap();
+        assert_eq!(offset.to_rfc3339(), "2025-01-01T10:30:00+00:00");
+
+        assert!(parse_timestamp(Some("yesterday".to_string())).is_none());
+        assert!(parse_timestamp(None).is_none());
+    }
+
     #[test]
     fn test_parse_config_json() {
         let json =
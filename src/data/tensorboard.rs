@@ -0,0 +1,754 @@
+//! TensorBoard storage backend for `tfevents` logs (read-only).
+//!
+//! Every directory containing `*tfevents*` files is a run. Its place under the
+//! root decides the project:
+//! - `<root>/<project>/<run...>/`: project `<project>`, run `<run...>`
+//! - `<root>/<run>/`: project named after the root directory, run `<run>`
+//! - `<root>/` itself: project and run both named after the root directory
+//!
+//! Only scalar summaries are read: legacy `simple_value`s and TF2 scalar
+//! tensors. Tags become metric names and event wall time becomes the point
+//! timestamp. Event files are re-parsed only when their size or modification
+//! time changes, and the tree is walked again only when the root or one of its
+//! subdirectories is modified.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use super::backend::StorageBackend;
+use super::models::{Metric, MetricPoint, Project, Run};
+
+/// Marker contained in every TensorBoard event file name
+const EVENT_FILE_MARKER: &str = "tfevents";
+
+/// Plugin name TensorBoard uses for scalar summaries
+const SCALARS_PLUGIN: &str = "scalars";
+
+/// TensorFlow `DataType` enum values for numeric tensors we can read
+const DT_FLOAT: u64 = 1;
+const DT_DOUBLE: u64 = 2;
+const DT_INT32: u64 = 3;
+const DT_INT64: u64 = 9;
+
+/// Size and modification time of each event file, used to detect changes
+type FilesSignature = Vec<(PathBuf, u64, Option<SystemTime>)>;
+
+/// Modification time of the root and each directory directly under it, used to
+/// detect added or removed runs
+type TreeSignature = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Storage backend reading TensorBoard event files from a directory tree
+pub struct TensorBoardBackend {
+    root: PathBuf,
+    /// Parsed runs keyed by run directory
+    cache: RefCell<HashMap<PathBuf, (FilesSignature, Vec<Metric>)>>,
+    /// Run directories found by the last walk of the tree
+    run_dirs: RefCell<Option<(TreeSignature, Vec<RunDir>)>>,
+}
+
+/// A run directory discovered under the root
+#[derive(Clone)]
+struct RunDir {
+    project: String,
+    run: String,
+    path: PathBuf,
+}
+
+impl TensorBoardBackend {
+    /// Create a backend reading event files under `root`
+    pub fn new(root: PathBuf) -> Self {
+        TensorBoardBackend {
+            root,
+            cache: RefCell::new(HashMap::new()),
+            run_dirs: RefCell::new(None),
+        }
+    }
+
+    /// Modification times of the root and its direct subdirectories. Creating
+    /// or removing a run changes at least one of them, for runs at most two
+    /// levels deep (`<project>/<run>`); deeper runs show up once a shallower
+    /// directory is touched.
+    fn tree_signature(&self) -> TreeSignature {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut signature = vec![(self.root.clone(), modified(&self.root))];
+        if let Ok(entries) = std::fs::read_dir(&self.root) {
+            let mut dirs: TreeSignature = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .map(|p| {
+                    let time = modified(&p);
+                    (p, time)
+                })
+                .collect();
+            dirs.sort();
+            signature.extend(dirs);
+        }
+        signature
+    }
+
+    /// Run directories under the root, reusing the last walk if the tree is unchanged
+    fn run_dirs(&self) -> Result<Vec<RunDir>> {
+        let signature = self.tree_signature();
+        if let Some((cached_signature, runs)) = self.run_dirs.borrow().as_ref() {
+            if *cached_signature == signature {
+                return Ok(runs.clone());
+            }
+        }
+        let runs = self.discover_runs()?;
+        *self.run_dirs.borrow_mut() = Some((signature, runs.clone()));
+        Ok(runs)
+    }
+
+    /// Find all run directories under the root
+    fn discover_runs(&self) -> Result<Vec<RunDir>> {
+        let mut dirs = Vec::new();
+        if self.root.exists() {
+            collect_event_dirs(&self.root, &mut HashSet::new(), &mut dirs)?;
+        }
+
+        let root_name = self
+            .root
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "tensorboard".to_string());
+
+        let mut runs: Vec<RunDir> = dirs
+            .into_iter()
+            .filter_map(|path| {
+                let relative = path.strip_prefix(&self.root).ok()?;
+                let parts: Vec<String> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                let (project, run) = match parts.as_slice() {
+                    [] => (root_name.clone(), root_name.clone()),
+                    [run] => (root_name.clone(), run.clone()),
+                    [project, rest @ ..] => (project.clone(), rest.join("/")),
+                };
+                Some(RunDir { project, run, path })
+            })
+            .collect();
+        runs.sort_by(|a, b| (&a.project, &a.run).cmp(&(&b.project, &b.run)));
+        Ok(runs)
+    }
+
+    fn find_run(&self, project: &str, run_id: &str) -> Result<RunDir> {
+        self.run_dirs()?
+            .into_iter()
+            .find(|r| r.project == project && r.run == run_id)
+            .with_context(|| format!("Run not found: {project}/{run_id}"))
+    }
+
+    /// Load a run's scalars, reusing the cached parse if its event files are unchanged
+    fn load_run_metrics(&self, dir: &Path) -> Result<Vec<Metric>> {
+        let files = event_files(dir)?;
+        let signature: FilesSignature = files
+            .iter()
+            .map(|f| {
+                let meta = std::fs::metadata(f).ok();
+                let len = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+                let modified = meta.and_then(|m| m.modified().ok());
+                (f.clone(), len, modified)
+            })
+            .collect();
+
+        if let Some((cached_signature, metrics)) = self.cache.borrow().get(dir) {
+            if *cached_signature == signature {
+                return Ok(metrics.clone());
+            }
+        }
+
+        let mut by_tag: BTreeMap<String, Metric> = BTreeMap::new();
+        for file in &files {
+            let data =
+                std::fs::read(file).with_context(|| format!("Failed to read file: {file:?}"))?;
+            for (tag, point) in parse_event_file(&data) {
+                by_tag
+                    .entry(tag.clone())
+                    .or_insert_with(|| Metric::new(tag))
+                    .points_mut()
+                    .push(point);
+            }
+        }
+
+        let mut metrics: Vec<Metric> = by_tag.into_values().collect();
+        for metric in &mut metrics {
+            metric.points_mut().sort_by_key(|p| (p.step, p.timestamp));
+        }
+
+        self.cache
+            .borrow_mut()
+            .insert(dir.to_path_buf(), (signature, metrics.clone()));
+        Ok(metrics)
+    }
+
+    /// Earliest event time of a run, used as its creation time
+    fn run_start(&self, dir: &Path) -> Option<DateTime<Utc>> {
+        self.load_run_metrics(dir)
+            .ok()?
+            .iter()
+            .filter_map(|m| m.points().iter().filter_map(|p| p.timestamp).min())
+            .min()
+    }
+}
+
+impl StorageBackend for TensorBoardBackend {
+    fn list_projects(&self) -> Result<Vec<Project>> {
+        let mut projects: BTreeMap<String, Project> = BTreeMap::new();
+        for run in self.run_dirs()? {
+            let created_at = self.run_start(&run.path);
+            let project = projects
+                .entry(run.project.clone())
+                .or_insert_with(|| Project::new(run.project.clone(), 0, None));
+            project.run_count += 1;
+            project.last_updated = project.last_updated.max(created_at);
+        }
+
+        let mut projects: Vec<Project> = projects.into_values().collect();
+        // Sort by last updated (most recent first)
+        projects.sort_by_key(|p| std::cmp::Reverse(p.last_updated));
+        Ok(projects)
+    }
+
+    fn list_runs(&self, project: &str) -> Result<Vec<Run>> {
+        let mut runs: Vec<Run> = self
+            .run_dirs()?
+            .into_iter()
+            .filter(|r| r.project == project)
+            .map(|r| {
+                let created_at = self.run_start(&r.path);
+                Run::new(r.run, project.to_string(), created_at, Vec::new())
+            })
+            .collect();
+        runs.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+        Ok(runs)
+    }
+
+    fn get_all_metrics(&self, project: &str, run_id: &str) -> Result<Vec<Metric>> {
+        let run = self.find_run(project, run_id)?;
+        self.load_run_metrics(&run.path)
+    }
+}
+
+/// Whether a file name looks like a TensorBoard event file
+fn is_event_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.contains(EVENT_FILE_MARKER) && !n.starts_with('.'))
+}
+
+/// Event files directly inside `dir`, in name order (which is creation order)
+fn event_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {dir:?}"))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && is_event_file(p))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Recursively collect directories that contain event files. Directories
+/// already `visited` (reached again through a symlink) are skipped, so
+/// symlink loops end.
+fn collect_event_dirs(
+    dir: &Path,
+    visited: &mut HashSet<PathBuf>,
+    out: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let canonical = dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve directory: {dir:?}"))?;
+    if !visited.insert(canonical) {
+        return Ok(());
+    }
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read directory: {dir:?}"))?;
+    let mut has_events = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_event_dirs(&path, visited, out)?;
+        } else if is_event_file(&path) {
+            has_events = true;
+        }
+    }
+    if has_events {
+        out.push(dir.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Parse all scalar points from the contents of an event file.
+///
+/// Event files are TFRecord streams: `u64 length`, `u32 length CRC`,
+/// `data`, `u32 data CRC`. CRCs are not checked; a truncated trailing record
+/// (a file still being written) ends the stream, and records that don't parse
+/// as events are skipped.
+fn parse_event_file(data: &[u8]) -> Vec<(String, MetricPoint)> {
+    let mut points = Vec::new();
+    // Tags known to hold non-scalar data (declared once, in the tag's first summary)
+    let mut plugin_by_tag: HashMap<String, String> = HashMap::new();
+
+    let mut pos = 0;
+    while pos + 12 <= data.len() {
+        let len = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap()) as usize;
+        let start = pos + 12;
+        let Some(end) = start.checked_add(len).filter(|end| {
+            end.checked_add(4)
+                .is_some_and(|crc_end| crc_end <= data.len())
+        }) else {
+            break;
+        };
+        // Keep a record's points only if all of it parses
+        let mut record = Vec::new();
+        if parse_event(&data[start..end], &mut plugin_by_tag, &mut record).is_ok() {
+            points.append(&mut record);
+        }
+        pos = end + 4;
+    }
+    points
+}
+
+/// Parse one `Event` message, appending its scalar summary values
+fn parse_event(
+    event: &[u8],
+    plugin_by_tag: &mut HashMap<String, String>,
+    points: &mut Vec<(String, MetricPoint)>,
+) -> Result<()> {
+    let mut wall_time = None;
+    let mut step = 0;
+    let mut summary = None;
+
+    for field in ProtoReader::new(event) {
+        match field? {
+            (1, WireValue::Fixed64(bits)) => wall_time = Some(f64::from_bits(bits)),
+            (2, WireValue::Varint(v)) => step = v as i64,
+            (5, WireValue::Bytes(bytes)) => summary = Some(bytes),
+            _ => {}
+        }
+    }
+
+    let Some(summary) = summary else {
+        return Ok(());
+    };
+    let timestamp = wall_time
+        .and_then(|t| DateTime::from_timestamp(t.trunc() as i64, (t.fract() * 1e9) as u32));
+
+    for field in ProtoReader::new(summary) {
+        if let (1, WireValue::Bytes(value)) = field? {
+            if let Some((tag, value)) = parse_summary_value(value, plugin_by_tag)? {
+                points.push((
+                    tag,
+                    MetricPoint {
+                        step,
+                        value,
+                        timestamp,
+                    },
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse a `Summary.Value`, returning (tag, value) if it is a scalar
+fn parse_summary_value(
+    value: &[u8],
+    plugin_by_tag: &mut HashMap<String, String>,
+) -> Result<Option<(String, f64)>> {
+    let mut tag = None;
+    let mut simple_value = None;
+    let mut tensor = None;
+    let mut plugin = None;
+
+    for field in ProtoReader::new(value) {
+        match field? {
+            (1, WireValue::Bytes(bytes)) => tag = Some(String::from_utf8_lossy(bytes).into_owned()),
+            (2, WireValue::Fixed32(bits)) => simple_value = Some(f64::from(f32::from_bits(bits))),
+            (8, WireValue::Bytes(bytes)) => tensor = Some(bytes),
+            (9, WireValue::Bytes(bytes)) => plugin = parse_plugin_name(bytes)?,
+            _ => {}
+        }
+    }
+
+    let Some(tag) = tag else {
+        return Ok(None);
+    };
+    if let Some(plugin) = plugin {
+        plugin_by_tag.insert(tag.clone(), plugin);
+    }
+    if let Some(v) = simple_value {
+        return Ok(Some((tag, v)));
+    }
+
+    let is_scalar_tag = plugin_by_tag
+        .get(&tag)
+        .is_none_or(|plugin| plugin == SCALARS_PLUGIN);
+    match tensor {
+        Some(tensor) if is_scalar_tag => Ok(parse_scalar_tensor(tensor)?.map(|v| (tag, v))),
+        _ => Ok(None),
+    }
+}
+
+/// Plugin name from a `SummaryMetadata` message
+fn parse_plugin_name(metadata: &[u8]) -> Result<Option<String>> {
+    for field in ProtoReader::new(metadata) {
+        if let (1, WireValue::Bytes(plugin_data)) = field? {
+            for field in ProtoReader::new(plugin_data) {
+                if let (1, WireValue::Bytes(name)) = field? {
+                    return Ok(Some(String::from_utf8_lossy(name).into_owned()));
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Value of a single-element numeric `TensorProto`
+fn parse_scalar_tensor(tensor: &[u8]) -> Result<Option<f64>> {
+    let mut dtype = 0;
+    let mut values: Vec<f64> = Vec::new();
+    let mut content: &[u8] = &[];
+
+    for field in ProtoReader::new(tensor) {
+        match field? {
+            (1, WireValue::Varint(v)) => dtype = v,
+            (4, WireValue::Bytes(bytes)) => content = bytes,
+            // float_val, packed or not
+            (5, WireValue::Fixed32(bits)) => values.push(f64::from(f32::from_bits(bits))),
+            (5, WireValue::Bytes(bytes)) => values.extend(
+                bytes
+                    .chunks_exact(4)
+                    .map(|c| f64::from(f32::from_le_bytes(c.try_into().unwrap()))),
+            ),
+            // double_val, packed or not
+            (6, WireValue::Fixed64(bits)) => values.push(f64::from_bits(bits)),
+            (6, WireValue::Bytes(bytes)) => values.extend(
+                bytes
+                    .chunks_exact(8)
+                    .map(|c| f64::from_le_bytes(c.try_into().unwrap())),
+            ),
+            // int_val / int64_val (non-packed only; scalars are written unpacked)
+            (7, WireValue::Varint(v)) => values.push(f64::from(v as i32)),
+            (10, WireValue::Varint(v)) => values.push(v as i64 as f64),
+            _ => {}
+        }
+    }
+
+    if values.is_empty() && !content.is_empty() {
+        let value = match (dtype, content.len()) {
+            (DT_FLOAT, 4) => f64::from(f32::from_le_bytes(content.try_into().unwrap())),
+            (DT_DOUBLE, 8) => f64::from_le_bytes(content.try_into().unwrap()),
+            (DT_INT32, 4) => f64::from(i32::from_le_bytes(content.try_into().unwrap())),
+            (DT_INT64, 8) => i64::from_le_bytes(content.try_into().unwrap()) as f64,
+            _ => return Ok(None),
+        };
+        return Ok(Some(value));
+    }
+
+    Ok(match values.as_slice() {
+        [v] => Some(*v),
+        _ => None,
+    })
+}
+
+/// A decoded protobuf field value
+enum WireValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+/// Minimal protobuf wire-format reader yielding (field number, value) pairs
+struct ProtoReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        ProtoReader { buf, pos: 0 }
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self
+                .buf
+                .get(self.pos)
+                .context("Truncated protobuf varint")?;
+            self.pos += 1;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        anyhow::bail!("Invalid protobuf varint")
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.buf.len())
+            .context("Truncated protobuf field")?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_field(&mut self) -> Result<(u64, WireValue<'a>)> {
+        let key = self.read_varint()?;
+        let value = match key & 0x7 {
+            0 => WireValue::Varint(self.read_varint()?),
+            1 => WireValue::Fixed64(u64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            2 => {
+                let len = self.read_varint()? as usize;
+                WireValue::Bytes(self.take(len)?)
+            }
+            5 => WireValue::Fixed32(u32::from_le_bytes(self.take(4)?.try_into().unwrap())),
+            wire_type => anyhow::bail!("Unsupported protobuf wire type {wire_type}"),
+        };
+        Ok((key >> 3, value))
+    }
+}
+
+impl<'a> Iterator for ProtoReader<'a> {
+    type Item = Result<(u64, WireValue<'a>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.buf.len() {
+            return None;
+        }
+        let field = self.read_field();
+        if field.is_err() {
+            // Stop after a malformed field instead of yielding garbage
+            self.pos = self.buf.len();
+        }
+        Some(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn varint(mut v: u64, out: &mut Vec<u8>) {
+        while v >= 0x80 {
+            out.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        out.push(v as u8);
+    }
+
+    fn bytes_field(field: u64, bytes: &[u8], out: &mut Vec<u8>) {
+        varint(field << 3 | 2, out);
+        varint(bytes.len() as u64, out);
+        out.extend_from_slice(bytes);
+    }
+
+    /// Encode an `Event` with one summary value
+    fn encode_event(wall_time: f64, step: u64, value: &[u8]) -> Vec<u8> {
+        let mut summary = Vec::new();
+        bytes_field(1, value, &mut summary);
+
+        let mut event = Vec::new();
+        varint(1 << 3 | 1, &mut event);
+        event.extend_from_slice(&wall_time.to_le_bytes());
+        varint(2 << 3, &mut event);
+        varint(step, &mut event);
+        bytes_field(5, &summary, &mut event);
+        event
+    }
+
+    /// Legacy scalar: `Summary.Value { tag, simple_value }`
+    fn simple_value(tag: &str, v: f32) -> Vec<u8> {
+        let mut value = Vec::new();
+        bytes_field(1, tag.as_bytes(), &mut value);
+        varint(2 << 3 | 5, &mut value);
+        value.extend_from_slice(&v.to_le_bytes());
+        value
+    }
+
+    /// TF2 summary: `Summary.Value { tag, tensor, metadata { plugin_data { plugin_name } } }`
+    fn tensor_value(tag: &str, plugin: Option<&str>, v: f32) -> Vec<u8> {
+        let mut tensor = Vec::new();
+        varint(1 << 3, &mut tensor);
+        varint(DT_FLOAT, &mut tensor);
+        bytes_field(4, &v.to_le_bytes(), &mut tensor);
+
+        let mut value = Vec::new();
+        bytes_field(1, tag.as_bytes(), &mut value);
+        bytes_field(8, &tensor, &mut value);
+        if let Some(plugin) = plugin {
+            let mut plugin_data = Vec::new();
+            bytes_field(1, plugin.as_bytes(), &mut plugin_data);
+            let mut metadata = Vec::new();
+            bytes_field(1, &plugin_data, &mut metadata);
+            bytes_field(9, &metadata, &mut value);
+        }
+        value
+    }
+
+    /// Wrap events into TFRecord framing (CRCs are zeroed; they are not checked)
+    fn tfrecord(events: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for event in events {
+            out.extend_from_slice(&(event.len() as u64).to_le_bytes());
+            out.extend_from_slice(&[0; 4]);
+            out.extend_from_slice(event);
+            out.extend_from_slice(&[0; 4]);
+        }
+        out
+    }
+
+    #[test]
+    fn test_parse_event_file_scalars() {
+        let data = tfrecord(&[
+            encode_event(1_700_000_000.5, 0, &simple_value("loss", 1.5)),
+            encode_event(
+                1_700_000_001.0,
+                1,
+                &tensor_value("acc", Some("scalars"), 0.25),
+            ),
+            // A histogram tag declares its plugin once; later values omit metadata
+            encode_event(
+                1_700_000_002.0,
+                1,
+                &tensor_value("w", Some("histograms"), 3.0),
+            ),
+            encode_event(1_700_000_003.0, 2, &tensor_value("w", None, 4.0)),
+            encode_event(1_700_000_004.0, 2, &tensor_value("acc", None, 0.5)),
+        ]);
+
+        let points = parse_event_file(&data);
+        let tags: Vec<&str> = points.iter().map(|(tag, _)| tag.as_str()).collect();
+        assert_eq!(tags, vec!["loss", "acc", "acc"]);
+        assert_eq!(points[0].1.value, 1.5);
+        assert_eq!(points[0].1.step, 0);
+        assert_eq!(
+            points[0].1.timestamp.unwrap().timestamp_millis(),
+            1_700_000_000_500
+        );
+        assert_eq!(points[2].1.value, 0.5);
+    }
+
+    #[test]
+    fn test_parse_event_file_truncated_record() {
+        let mut data = tfrecord(&[encode_event(1.0, 0, &simple_value("loss", 1.0))]);
+        // Half-written second record
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data.extend_from_slice(&[0; 10]);
+
+        let points = parse_event_file(&data);
+        assert_eq!(points.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_event_file_skips_corrupt_records() {
+        let mut data = tfrecord(&[
+            encode_event(1.0, 0, &simple_value("loss", 1.0)),
+            // A valid event followed by a bytes field claiming more bytes
+            // than the record holds
+            [
+                encode_event(1.5, 5, &simple_value("loss", 9.0)),
+                vec![5 << 3 | 2, 100],
+            ]
+            .concat(),
+            encode_event(2.0, 1, &simple_value("loss", 0.5)),
+        ]);
+        // A corrupt length that would overflow
+        data.extend_from_slice(&(u64::MAX - 8).to_le_bytes());
+        data.extend_from_slice(&[0; 8]);
+
+        let points = parse_event_file(&data);
+        let steps: Vec<i64> = points.iter().map(|(_, p)| p.step).collect();
+        assert_eq!(steps, vec![0, 1]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loop_is_not_followed() {
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = dir.path().join("run");
+        std::fs::create_dir(&run_dir).unwrap();
+        std::fs::write(
+            run_dir.join("events.out.tfevents.1.host"),
+            tfrecord(&[encode_event(1.0, 0, &simple_value("loss", 1.0))]),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(dir.path(), run_dir.join("loop")).unwrap();
+
+        let backend = TensorBoardBackend::new(dir.path().to_path_buf());
+        let runs = backend.discover_runs().unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].run, "run");
+    }
+
+    #[test]
+    fn test_backend_maps_directories_to_projects_and_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let write_run = |relative: &str, values: &[(u64, f32)]| {
+            let run_dir = dir.path().join(relative);
+            std::fs::create_dir_all(&run_dir).unwrap();
+            let events: Vec<Vec<u8>> = values
+                .iter()
+                .map(|&(step, v)| encode_event(1e9 + step as f64, step, &simple_value("loss", v)))
+                .collect();
+            std::fs::write(
+                run_dir.join("events.out.tfevents.1.host"),
+                tfrecord(&events),
+            )
+            .unwrap();
+        };
+        write_run("vision/resnet/seed0", &[(0, 1.0), (1, 0.5)]);
+        write_run("vision/vit", &[(0, 2.0)]);
+        write_run("baseline", &[(0, 3.0)]);
+
+        let backend = TensorBoardBackend::new(dir.path().to_path_buf());
+
+        let projects = backend.list_projects().unwrap();
+        let mut names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        names.sort();
+        let root_name = dir.path().file_name().unwrap().to_str().unwrap();
+        let mut expected = vec![root_name, "vision"];
+        expected.sort();
+        assert_eq!(names, expected);
+
+        let mut runs: Vec<String> = backend
+            .list_runs("vision")
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        runs.sort();
+        assert_eq!(runs, vec!["resnet/seed0", "vit"]);
+
+        let metrics = backend.get_all_metrics("vision", "resnet/seed0").unwrap();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].name, "loss");
+        assert_eq!(metrics[0].points().len(), 2);
+
+        let baseline = backend.get_all_metrics(root_name, "baseline").unwrap();
+        assert_eq!(baseline[0].points()[0].value, 3.0);
+
+        // The walk is reused until the tree changes, then a new run is found
+        let cached = backend.run_dirs.borrow().as_ref().unwrap().0.clone();
+        backend.list_runs("vision").unwrap();
+        assert_eq!(backend.run_dirs.borrow().as_ref().unwrap().0, cached);
+        write_run("vision/deit", &[(0, 1.0)]);
+        let runs = backend.list_runs("vision").unwrap();
+        assert_eq!(runs.len(), 3);
+        assert!(backend.get_all_metrics("vision", "deit").is_ok());
+    }
+}
@@ -0,0 +1,186 @@
+//! File-system watcher for the data directories.
+//!
+//! Used as an opt-in alternative to timer-based polling: the dashboard only
+//! refreshes when a data file of the `--source` format changes (for trackio, a
+//! project database or its write-ahead log).
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+use anyhow::{Context, Result};
+use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::cli::DataSource;
+
+/// Watches data directories for changes to the files of a data source
+pub struct DbWatcher {
+    /// Kept alive for as long as events should be delivered
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    source: DataSource,
+}
+
+impl DbWatcher {
+    /// Start watching the given directories for changes to `source` data files,
+    /// recursively for sources that keep runs in subdirectories.
+    /// Directories that don't exist are skipped; at least one must be watchable.
+    pub fn new(db_paths: &[PathBuf], source: DataSource) -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            // Receiver is gone once the app exits; nothing left to notify
+            let _ = tx.send(event);
+        })
+        .context("Failed to create file watcher")?;
+        let mut watched = 0;
+        for db_path in db_paths.iter().filter(|p| p.exists()) {
+            watcher
+                .watch(db_path, recursive_mode(source))
+                .with_context(|| format!("Failed to watch directory: {db_path:?}"))?;
+            watched += 1;
+        }
+        if watched == 0 {
+            anyhow::bail!("No data directory to watch");
+        }
+
+        Ok(DbWatcher {
+            _watcher: watcher,
+            events,
+            source,
+        })
+    }
+
+    /// Drain pending events and report whether any data file changed.
+    /// Returns an error if the watcher failed and can no longer be relied upon.
+    pub fn has_changes(&self) -> Result<bool> {
+        let mut changed = false;
+        loop {
+            match self.events.try_recv() {
+                Ok(Ok(event)) => changed |= is_relevant_event(&event, self.source),
+                Ok(Err(e)) => return Err(e).context("File watcher error"),
+                Err(TryRecvError::Empty) => return Ok(changed),
+                Err(TryRecvError::Disconnected) => anyhow::bail!("File watcher stopped"),
+            }
+        }
+    }
+}
+
+/// How deep data files of a source can sit under a data directory
+fn recursive_mode(source: DataSource) -> RecursiveMode {
+    match source {
+        DataSource::Trackio | DataSource::Parquet => RecursiveMode::NonRecursive,
+        // Runs (or projects) are subdirectories
+        DataSource::Tensorboard | DataSource::Mlflow | DataSource::Logs => RecursiveMode::Recursive,
+    }
+}
+
+/// Whether an event signals new data: a data file of `source` was created or written.
+/// Access and metadata events are ignored, since our own reads produce them.
+fn is_relevant_event(event: &Event, source: DataSource) -> bool {
+    let is_write = match event.kind {
+        EventKind::Create(_) => true,
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        EventKind::Modify(_) => true,
+        _ => false,
+    };
+    is_write && event.paths.iter().any(|p| is_data_file(p, source))
+}
+
+/// Whether a path is a file `source` reads data from
+fn is_data_file(path: &Path, source: DataSource) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    if name.starts_with('.') {
+        return false;
+    }
+    match source {
+        DataSource::Trackio => is_project_db_file(path),
+        DataSource::Parquet => name.ends_with(".parquet"),
+        DataSource::Tensorboard => name.contains("tfevents"),
+        // Metrics, params and meta.yaml files; metric files have no extension
+        DataSource::Mlflow => true,
+        DataSource::Logs => [".csv", ".jsonl", ".json"]
+            .iter()
+            .any(|ext| name.ends_with(ext)),
+    }
+}
+
+/// Whether a path is a project `.db` file or its `-wal` journal
+fn is_project_db_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    !name.starts_with('.') && (name.ends_with(".db") || name.ends_with(".db-wal"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_is_project_db_file() {
+        assert!(is_project_db_file(Path::new("/tmp/trackio/proj.db")));
+        assert!(is_project_db_file(Path::new("/tmp/trackio/proj.db-wal")));
+        assert!(!is_project_db_file(Path::new("/tmp/trackio/proj.db-shm")));
+        assert!(!is_project_db_file(Path::new("/tmp/trackio/.hidden.db")));
+        assert!(!is_project_db_file(Path::new("/tmp/trackio/notes.txt")));
+    }
+
+    #[test]
+    fn test_is_data_file_per_source() {
+        let path = Path::new;
+        assert!(is_data_file(
+            path("/data/proj.parquet"),
+            DataSource::Parquet
+        ));
+        assert!(!is_data_file(path("/data/proj.db"), DataSource::Parquet));
+        assert!(is_data_file(
+            path("/logs/run-1/events.out.tfevents.1.host"),
+            DataSource::Tensorboard
+        ));
+        assert!(is_data_file(
+            path("/mlruns/0/abc/metrics/loss"),
+            DataSource::Mlflow
+        ));
+        assert!(!is_data_file(path("/mlruns/.trash"), DataSource::Mlflow));
+        assert!(is_data_file(path("/logs/proj/run.jsonl"), DataSource::Logs));
+        assert!(!is_data_file(
+            path("/logs/proj/notes.txt"),
+            DataSource::Logs
+        ));
+    }
+
+    #[test]
+    fn test_detects_new_project_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let watcher = DbWatcher::new(&[dir.path().to_path_buf()], DataSource::Trackio).unwrap();
+
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+        std::fs::write(dir.path().join("proj.db"), "data").unwrap();
+        assert!(wait_for_change(&watcher));
+    }
+
+    #[test]
+    fn test_detects_nested_event_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let run_dir = dir.path().join("exp").join("run-1");
+        std::fs::create_dir_all(&run_dir).unwrap();
+        let watcher = DbWatcher::new(&[dir.path().to_path_buf()], DataSource::Tensorboard).unwrap();
+
+        std::fs::write(run_dir.join("events.out.tfevents.1.host"), "data").unwrap();
+        assert!(wait_for_change(&watcher));
+    }
+
+    /// Poll until the watcher reports a change (events are delivered asynchronously)
+    fn wait_for_change(watcher: &DbWatcher) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if watcher.has_changes().unwrap() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+}
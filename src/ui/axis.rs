@@ -0,0 +1,177 @@
+//! Axis ticks at round numbers, and number formatting for chart labels and
+//! the cursor readout.
+
+use crate::cli::ValueFormat;
+
+/// Largest value written without an SI suffix
+const MAX_PLAIN: f64 = 1e4;
+
+/// Smallest non-zero value written without an exponent
+const MIN_PLAIN: f64 = 1e-3;
+
+/// Smallest value written in scientific notation rather than with an SI suffix
+const MAX_SI: f64 = 1e15;
+
+/// SI suffixes for large values, by power of 1000
+const SI_SUFFIXES: [(f64, &str); 4] = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k")];
+
+/// Most decimals ever shown
+const MAX_DECIMALS: i32 = 9;
+
+/// At most `max_ticks` (at least 2) ticks covering `lo..=hi`, a round step
+/// (1, 2, 2.5 or 5 × 10ⁿ, and at least `min_step`) apart. The first and last tick
+/// enclose the range, so they make good axis bounds.
+pub fn nice_ticks(lo: f64, hi: f64, max_ticks: usize, min_step: f64) -> Vec<f64> {
+    let max_ticks = max_ticks.max(2);
+    if hi <= lo || !lo.is_finite() || !hi.is_finite() {
+        return vec![lo, hi];
+    }
+    let raw_step = ((hi - lo) / (max_ticks - 1) as f64).max(min_step);
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    for multiple in [1.0, 2.0, 2.5, 5.0, 10.0, 20.0] {
+        let step = multiple * magnitude;
+        // Whole minimum steps (steps, decades) keep whole steps
+        if step < raw_step || (min_step >= 1.0 && step.fract() != 0.0) {
+            continue;
+        }
+        let first = (lo / step).floor();
+        let last = (hi / step).ceil();
+        let count = (last - first) as usize + 1;
+        if count <= max_ticks {
+            // Multiplying whole numbers of steps avoids drift, and + 0.0 turns -0 into 0
+            return (0..count)
+                .map(|i| (first + i as f64) * step + 0.0)
+                .collect();
+        }
+    }
+    vec![lo, hi]
+}
+
+/// `count` (at least 2) evenly spaced ticks from `lo` to `hi`, for ranges
+/// that must stay as they are (a zoomed or locked view)
+pub fn even_ticks(lo: f64, hi: f64, count: usize) -> Vec<f64> {
+    let count = count.max(2);
+    (0..count)
+        .map(|i| lo + (hi - lo) * i as f64 / (count - 1) as f64)
+        .collect()
+}
+
+/// Precision to label `ticks` with: their spacing if it is round (as from
+/// `nice_ticks`), a tenth of it otherwise
+pub fn tick_precision(ticks: &[f64]) -> f64 {
+    let step = match ticks {
+        [first, second, ..] => (second - first).abs(),
+        _ => return 0.0,
+    };
+    let mantissa = step / 10f64.powf(step.log10().floor());
+    if [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .any(|m| (mantissa - m).abs() < 1e-6)
+    {
+        step
+    } else {
+        step / 10.0
+    }
+}
+
+/// Decimals needed to tell apart values `step` apart
+fn decimals(step: f64) -> usize {
+    if step <= 0.0 || !step.is_finite() {
+        return 2;
+    }
+    // The small tolerance keeps steps like 0.1 from needing two decimals
+    (-(step.log10() + 1e-9).floor()).clamp(0.0, MAX_DECIMALS as f64) as usize
+}
+
+/// `value` in scientific notation, with as many digits as a precision of `step` needs
+fn scientific(value: f64, step: f64) -> String {
+    let exponent = value.abs().log10().floor();
+    let digits = decimals(step / 10f64.powf(exponent));
+    format!("{value:.digits$e}")
+}
+
+/// Write `value` in `format`, precise enough to tell apart values `step` apart
+/// (e.g. neighbouring ticks). `Auto` picks plain decimals, SI suffixes for
+/// large values (`3.2M`) or scientific notation for tiny ones (`1.5e-5`).
+pub fn format_value(value: f64, step: f64, format: ValueFormat) -> String {
+    if !value.is_finite() {
+        return format!("{value}");
+    }
+    match format {
+        ValueFormat::Fixed(digits) => format!("{value:.digits$}"),
+        ValueFormat::Percent => {
+            let digits = decimals(step * 100.0);
+            format!("{:.digits$}%", value * 100.0)
+        }
+        ValueFormat::Scientific if value == 0.0 => "0".to_string(),
+        ValueFormat::Scientific => scientific(value, step),
+        ValueFormat::Auto => {
+            let magnitude = value.abs();
+            if value == 0.0 {
+                "0".to_string()
+            } else if (MAX_PLAIN..MAX_SI).contains(&magnitude) {
+                let (unit, suffix) = SI_SUFFIXES
+                    .iter()
+                    .find(|(unit, _)| magnitude >= *unit)
+                    .copied()
+                    .unwrap_or((1.0, ""));
+                let digits = decimals(step / unit);
+                format!("{:.digits$}{suffix}", value / unit)
+            } else if !(MIN_PLAIN..MAX_SI).contains(&magnitude) {
+                scientific(value, step)
+            } else {
+                let digits = decimals(step);
+                format!("{value:.digits$}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_ticks() {
+        assert_eq!(
+            nice_ticks(0.0, 199.0, 5, 0.0),
+            [0.0, 50.0, 100.0, 150.0, 200.0]
+        );
+        assert_eq!(
+            nice_ticks(0.13, 0.87, 6, 0.0),
+            [0.0, 0.2, 0.4, 0.6000000000000001, 0.8, 1.0]
+        );
+        assert_eq!(nice_ticks(-3.2, 1.5, 3, 0.0), [-5.0, 0.0, 5.0]);
+        assert_eq!(nice_ticks(0.13, 0.89, 5, 0.0), [0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(nice_ticks(0.0, 7.0, 4, 1.0), [0.0, 5.0, 10.0]);
+        // A minimum step keeps log ticks at whole decades
+        assert_eq!(nice_ticks(-5.0, -4.2, 6, 1.0), [-5.0, -4.0]);
+        assert_eq!(nice_ticks(2.0, 2.0, 5, 0.0), [2.0, 2.0]);
+        assert_eq!(even_ticks(10.0, 20.0, 3), [10.0, 15.0, 20.0]);
+        assert_eq!(tick_precision(&[0.0, 0.2, 0.4]), 0.2);
+        assert_eq!(tick_precision(&[10.0, 13.0]), 0.3);
+        assert_eq!(tick_precision(&[0.0, 0.25]), 0.025);
+    }
+
+    #[test]
+    fn test_format_value() {
+        let auto = ValueFormat::Auto;
+        assert_eq!(format_value(0.5, 0.1, auto), "0.5");
+        assert_eq!(format_value(150.0, 50.0, auto), "150");
+        assert_eq!(format_value(3_000_000.0, 1e6, auto), "3M");
+        assert_eq!(format_value(2_500_000.0, 5e5, auto), "2.5M");
+        assert_eq!(format_value(15_000.0, 5000.0, auto), "15k");
+        assert_eq!(format_value(1e-5, 1e-5, auto), "1e-5");
+        assert_eq!(format_value(2.5e-5, 5e-6, auto), "2.5e-5");
+        assert_eq!(format_value(0.0, 0.1, auto), "0");
+        assert_eq!(format_value(f64::NAN, 0.1, auto), "NaN");
+
+        assert_eq!(format_value(0.934, 0.001, ValueFormat::Percent), "93.4%");
+        assert_eq!(format_value(0.5, 0.1, ValueFormat::Percent), "50%");
+        assert_eq!(format_value(0.5, 0.1, ValueFormat::Fixed(3)), "0.500");
+        assert_eq!(
+            format_value(1234.0, 1.0, ValueFormat::Scientific),
+            "1.234e3"
+        );
+    }
+}
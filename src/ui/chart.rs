@@ -1,15 +1,25 @@
 //! Metrics chart widget for visualizing training metrics.
 
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     symbols::Marker,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    widgets::{
+        Axis, Block, BorderType, Borders, Cell as TableCell, Chart, Dataset, GraphType, Paragraph,
+        Row, Table,
+    },
     Frame,
 };
 
-use crate::data::Metric;
+use super::axis;
+use super::smoothing::Smoothing;
+use crate::cli::{DisplayTimezone, MetricFormat, ResumeMode, ValueFormat};
+use crate::data::{Metric, MetricPoint};
 
 /// Colorblind-friendly palette (256-color approximation of Wong palette)
 const COLORS: [Color; 6] = [
@@ -29,19 +39,739 @@ const MARKERS: [Marker; 4] = [
     Marker::Quadrant,
 ];
 
+/// Color of the markers at steps with non-finite values
+const NON_FINITE_COLOR: Color = Color::Red;
+
+/// Color of the vertical lines at steps where a run was resumed
+const RESUME_COLOR: Color = Color::DarkGray;
+
+/// Color of the step cursor line
+const CURSOR_COLOR: Color = Color::White;
+
+/// Factor the visible x-range changes by per zoom step
+const ZOOM_FACTOR: f64 = 2.0;
+
+/// Narrowest visible x-range, as a fraction of the data's
+const MIN_ZOOM_FRACTION: f64 = 1.0 / 4096.0;
+
+/// Fraction of the visible x-range moved per pan step
+const PAN_FRACTION: f64 = 0.25;
+
+/// Most ticks on an axis
+const MAX_TICKS: u16 = 8;
+
+/// Rows per y-axis tick, at least
+const Y_TICK_SPACING: u16 = 3;
+
+/// Columns per x-axis tick, at least, for steps and metric values
+const X_TICK_SPACING: u16 = 12;
+
+/// Columns per x-axis tick, at least, for times
+const TIME_TICK_SPACING: u16 = 16;
+
+/// Precision of readout values, relative to the value (four significant digits)
+const READOUT_PRECISION: f64 = 1e-3;
+
 /// Metric data selected for display.
 /// Tuple: (run_name, run_idx, metric_idx, metric)
 type RunMetric<'a> = (String, usize, usize, &'a Metric);
 
+/// A line to draw.
+/// Tuple: (run_idx, metric_idx, whether drawn dimmed, points)
+type ChartLine = (usize, usize, bool, Vec<(f64, f64)>);
+
+/// What the chart's x-axis shows
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum XAxis {
+    #[default]
+    Step,
+    /// Wall-clock time of each point
+    WallTime,
+    /// Time since the run's first logged point
+    RelativeTime,
+    /// Value of another metric of the same run, joined by step
+    Metric(String),
+}
+
+impl XAxis {
+    /// The next of step, wall time and relative time (a metric axis goes back to step)
+    pub fn next_time_axis(&self) -> Self {
+        match self {
+            XAxis::Step => XAxis::WallTime,
+            XAxis::WallTime => XAxis::RelativeTime,
+            XAxis::RelativeTime | XAxis::Metric(_) => XAxis::Step,
+        }
+    }
+
+    /// Axis title
+    fn title(&self) -> &str {
+        match self {
+            XAxis::Step => "step",
+            XAxis::WallTime => "time",
+            XAxis::RelativeTime => "elapsed",
+            XAxis::Metric(name) => name,
+        }
+    }
+
+    /// Label for an x coordinate, on an axis spanning `span`. Steps and metric
+    /// values are written in `format`, precise to `precision`; times are shown in `timezone`.
+    fn label(
+        &self,
+        x: f64,
+        span: f64,
+        precision: f64,
+        format: ValueFormat,
+        timezone: DisplayTimezone,
+    ) -> String {
+        match self {
+            XAxis::Step => axis::format_value(x, precision.max(1.0), format),
+            XAxis::WallTime => {
+                // Seconds only matter over short spans, the date over long ones
+                let fmt = if span < 600.0 {
+                    "%H:%M:%S"
+                } else if span < 86400.0 {
+                    "%H:%M"
+                } else {
+                    "%m-%d %H:%M"
+                };
+                DateTime::from_timestamp_millis((x * 1000.0) as i64)
+                    .map(|t| timezone.format(t, fmt))
+                    .unwrap_or_default()
+            }
+            XAxis::RelativeTime => format_duration(x),
+            XAxis::Metric(_) => axis::format_value(x, precision, format),
+        }
+    }
+}
+
+/// Scale of the chart's y-axis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum YScale {
+    #[default]
+    Linear,
+    /// log10; non-positive values can't be shown
+    Log,
+    /// sign(y) * log10(1 + |y|): logarithmic away from zero, linear near it
+    Symlog,
+}
+
+impl YScale {
+    /// The next scale, for cycling with a key
+    pub fn next(self) -> Self {
+        match self {
+            YScale::Linear => YScale::Log,
+            YScale::Log => YScale::Symlog,
+            YScale::Symlog => YScale::Linear,
+        }
+    }
+
+    /// Name shown on the y-axis, if not linear
+    fn name(self) -> &'static str {
+        match self {
+            YScale::Linear => "",
+            YScale::Log => "log",
+            YScale::Symlog => "symlog",
+        }
+    }
+
+    /// Position of a value on the axis, or `None` if the scale can't show it.
+    /// Non-finite values are kept as they are, to be marked.
+    fn transform(self, y: f64) -> Option<f64> {
+        match self {
+            YScale::Linear => Some(y),
+            _ if !y.is_finite() => Some(y),
+            YScale::Log => (y > 0.0).then(|| y.log10()),
+            YScale::Symlog => Some(y.signum() * y.abs().ln_1p() / std::f64::consts::LN_10),
+        }
+    }
+
+    /// Value at a position on the axis
+    fn inverse(self, t: f64) -> f64 {
+        match self {
+            YScale::Linear => t,
+            YScale::Log => 10f64.powf(t),
+            YScale::Symlog => t.signum() * (10f64.powf(t.abs()) - 1.0),
+        }
+    }
+
+    /// Labels for ticks at positions `ticks` on the axis, with values written in `format`
+    fn labels(self, ticks: &[f64], format: ValueFormat) -> Vec<String> {
+        if self == YScale::Linear {
+            let precision = axis::tick_precision(ticks);
+            return ticks
+                .iter()
+                .map(|&t| axis::format_value(t, precision, format))
+                .collect();
+        }
+        let values: Vec<f64> = ticks.iter().map(|&t| self.inverse(t)).collect();
+        (0..values.len())
+            .map(|i| {
+                // Whole decades are exact; other ticks need to be told apart
+                // from their neighbours
+                let precision = if ticks[i].fract() == 0.0 {
+                    values[i].abs()
+                } else {
+                    [i.checked_sub(1), Some(i + 1)]
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|j| values.get(j))
+                        .map(|v| (v - values[i]).abs())
+                        .fold(f64::INFINITY, f64::min)
+                };
+                axis::format_value(values[i], precision, format)
+            })
+            .collect()
+    }
+}
+
+/// Human-readable duration such as `45s`, `12m30s`, `1h20m` or `2d3h`
+fn format_duration(seconds: f64) -> String {
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let total = seconds.abs().round() as u64;
+    let (days, hours, minutes, secs) = (
+        total / 86400,
+        total % 86400 / 3600,
+        total % 3600 / 60,
+        total % 60,
+    );
+    // The two largest units are enough for a tick label
+    let text = match (days, hours, minutes) {
+        (0, 0, 0) => format!("{secs}s"),
+        (0, 0, _) if secs == 0 => format!("{minutes}m"),
+        (0, 0, _) => format!("{minutes}m{secs}s"),
+        (0, _, 0) => format!("{hours}h"),
+        (0, _, _) => format!("{hours}h{minutes}m"),
+        (_, 0, _) => format!("{days}d"),
+        _ => format!("{days}d{hours}h"),
+    };
+    format!("{sign}{text}")
+}
+
+/// Per-run data needed to place points on a non-step x-axis
+#[derive(Debug, Clone, Default)]
+pub struct RunAxis<'a> {
+    /// Time of the run's first logged point, for relative time
+    pub start: Option<DateTime<Utc>>,
+    /// The run's x-axis metric, for a metric axis
+    pub x_metric: Option<&'a Metric>,
+}
+
+impl<'a> RunAxis<'a> {
+    /// Axis data for a run with the given metrics
+    pub fn new(metrics: &'a [Metric], x_axis: &XAxis) -> Self {
+        let start = metrics
+            .iter()
+            .flat_map(|m| m.points().iter())
+            .filter_map(|p| p.timestamp)
+            .min();
+        let x_metric = match x_axis {
+            XAxis::Metric(name) => metrics.iter().find(|m| &m.name == name),
+            _ => None,
+        };
+        RunAxis { start, x_metric }
+    }
+}
+
+/// Chart coordinates of `points` on `x_axis`. Points without an x value
+/// (no timestamp, or no finite x metric at their step) are left out.
+fn coordinates(points: &[&MetricPoint], x_axis: &XAxis, run: &RunAxis) -> Vec<(f64, f64)> {
+    let seconds = |t: DateTime<Utc>| t.timestamp_millis() as f64 / 1000.0;
+    match x_axis {
+        XAxis::Step => points.iter().map(|p| (p.step as f64, p.value)).collect(),
+        XAxis::WallTime => points
+            .iter()
+            .filter_map(|p| Some((seconds(p.timestamp?), p.value)))
+            .collect(),
+        XAxis::RelativeTime => {
+            let Some(start) = run.start else {
+                return Vec::new();
+            };
+            points
+                .iter()
+                .filter_map(|p| Some((seconds(p.timestamp?) - seconds(start), p.value)))
+                .collect()
+        }
+        XAxis::Metric(_) => {
+            let Some(x_metric) = run.x_metric else {
+                return Vec::new();
+            };
+            // Later writes of a step win, as in the default resume mode
+            let x_by_step: HashMap<i64, f64> = x_metric
+                .latest_writes()
+                .into_iter()
+                .filter(|p| p.value.is_finite())
+                .map(|p| (p.step, p.value))
+                .collect();
+            points
+                .iter()
+                .filter_map(|p| Some((*x_by_step.get(&p.step)?, p.value)))
+                .collect()
+        }
+    }
+}
+
+/// Bounds of the last drawn chart, in chart coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DrawnBounds {
+    /// X-range of all data
+    data_x: (f64, f64),
+    x: (f64, f64),
+    y: (f64, f64),
+    /// Terminal cells the lines are drawn in
+    plot: Rect,
+}
+
+/// Zoom and pan state of the metrics chart. The ranges are kept as they are
+/// when data is refreshed, so new points past the visible range don't move it.
+#[derive(Debug, Default)]
+pub struct ChartView {
+    /// Visible x-range; `None` fits all data
+    x_range: Option<(f64, f64)>,
+    /// Locked y-range; `None` fits the points in the visible x-range
+    y_range: Option<(f64, f64)>,
+    /// Step cursor, at a logged x position; `None` hides it
+    cursor: Option<f64>,
+    /// Where a mouse drag to zoom into started
+    drag_start: Option<f64>,
+    /// Bounds drawn by the last frame, which zooming and panning start from
+    drawn: Cell<Option<DrawnBounds>>,
+    /// Logged x positions of the last frame, sorted, which the cursor snaps to
+    drawn_xs: RefCell<Vec<f64>>,
+    /// Smoothed lines of the last frame, reused while their points are unchanged
+    smoothed: RefCell<HashMap<LineKey, SmoothedLine>>,
+}
+
+/// A chart line: run name, metric name and index among the metric's lines
+type LineKey = (String, String, usize);
+
+/// A smoothed line with the smoothing and points it was computed from
+#[derive(Debug)]
+struct SmoothedLine {
+    smoothing: Smoothing,
+    points: Vec<(f64, f64)>,
+    smoothed: Vec<(f64, f64)>,
+}
+
+/// Whether two lines have bit-for-bit the same points (NaN included)
+fn same_points(a: &[(f64, f64)], b: &[(f64, f64)]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(p, q)| p.0.to_bits() == q.0.to_bits() && p.1.to_bits() == q.1.to_bits())
+}
+
+impl ChartView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fit all data again and hide the cursor
+    pub fn reset(&mut self) {
+        self.x_range = None;
+        self.y_range = None;
+        self.cursor = None;
+    }
+
+    /// Show the cursor at the last visible logged step, or hide it
+    pub fn toggle_cursor(&mut self) {
+        self.cursor = match self.cursor {
+            Some(_) => None,
+            None => {
+                let (lo, hi) = self.x_range.unwrap_or((f64::MIN, f64::MAX));
+                let xs = self.drawn_xs.borrow();
+                xs.iter().rev().find(|&&x| x >= lo && x <= hi).copied()
+            }
+        };
+    }
+
+    /// Move the cursor by `steps` logged steps, panning to keep it in view
+    pub fn move_cursor(&mut self, steps: isize) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        let xs = self.drawn_xs.borrow();
+        let Some(nearest) = nearest_index(&xs, cursor) else {
+            return;
+        };
+        let x = xs[nearest.saturating_add_signed(steps).min(xs.len() - 1)];
+        self.cursor = Some(x);
+
+        if let (Some((lo, hi)), Some(drawn)) = (self.x_range, self.drawn.get()) {
+            let width = hi - lo;
+            if x < lo {
+                self.x_range = Some(clamp_range((x, x + width), drawn.data_x));
+            } else if x > hi {
+                self.x_range = Some(clamp_range((x - width, x), drawn.data_x));
+            }
+        }
+    }
+
+    /// Narrow (`zoom_in`) or widen the visible x-range around its center.
+    /// Widening past the data's range fits all data again.
+    pub fn zoom(&mut self, zoom_in: bool) {
+        let Some(drawn) = self.drawn.get() else {
+            return;
+        };
+        let data_width = drawn.data_x.1 - drawn.data_x.0;
+        let width = if zoom_in {
+            (drawn.x.1 - drawn.x.0) / ZOOM_FACTOR
+        } else {
+            (drawn.x.1 - drawn.x.0) * ZOOM_FACTOR
+        };
+        if width >= data_width {
+            self.x_range = None;
+        } else if width >= data_width * MIN_ZOOM_FRACTION {
+            let center = (drawn.x.0 + drawn.x.1) / 2.0;
+            let range = (center - width / 2.0, center + width / 2.0);
+            self.x_range = Some(clamp_range(range, drawn.data_x));
+        }
+    }
+
+    /// Move the visible x-range left (negative `direction`) or right, within the data
+    pub fn pan(&mut self, direction: f64) {
+        let (Some((lo, hi)), Some(drawn)) = (self.x_range, self.drawn.get()) else {
+            return;
+        };
+        let shift = (hi - lo) * PAN_FRACTION * direction;
+        self.x_range = Some(clamp_range((lo + shift, hi + shift), drawn.data_x));
+    }
+
+    /// Chart x position under terminal column `column`, clamped to the plot
+    fn x_at_column(drawn: &DrawnBounds, column: u16) -> f64 {
+        let offset =
+            column.clamp(drawn.plot.x, drawn.plot.right().saturating_sub(1)) - drawn.plot.x;
+        let fraction = offset as f64 / drawn.plot.width.saturating_sub(1).max(1) as f64;
+        drawn.x.0 + fraction * (drawn.x.1 - drawn.x.0)
+    }
+
+    /// Chart x position under a terminal cell, if it is in the plot
+    fn x_at(&self, column: u16, row: u16) -> Option<f64> {
+        let drawn = self.drawn.get()?;
+        drawn
+            .plot
+            .contains(Position::new(column, row))
+            .then(|| Self::x_at_column(&drawn, column))
+    }
+
+    /// Move a shown cursor to the logged step nearest the mouse
+    pub fn hover(&mut self, column: u16, row: u16) {
+        if self.cursor.is_none() {
+            return;
+        }
+        let Some(x) = self.x_at(column, row) else {
+            return;
+        };
+        let xs = self.drawn_xs.borrow();
+        if let Some(nearest) = nearest_index(&xs, x) {
+            self.cursor = Some(xs[nearest]);
+        }
+    }
+
+    /// Start a mouse drag selecting a range to zoom into; returns whether
+    /// the mouse is over the plot
+    pub fn start_drag(&mut self, column: u16, row: u16) -> bool {
+        self.drag_start = self.x_at(column, row);
+        self.drag_start.is_some()
+    }
+
+    /// Zoom into the range dragged over since `start_drag`. A click without
+    /// dragging across a column doesn't zoom.
+    pub fn end_drag(&mut self, column: u16) {
+        let (Some(start), Some(drawn)) = (self.drag_start.take(), self.drawn.get()) else {
+            return;
+        };
+        let end = Self::x_at_column(&drawn, column);
+        let (lo, hi) = (start.min(end), start.max(end));
+        let column_width = (drawn.x.1 - drawn.x.0) / drawn.plot.width.max(1) as f64;
+        let data_width = drawn.data_x.1 - drawn.data_x.0;
+        if hi - lo >= column_width && hi - lo >= data_width * MIN_ZOOM_FRACTION {
+            self.x_range = Some((lo, hi));
+        }
+    }
+
+    /// Lock the y-range to what is drawn, or unlock it to fit the visible points
+    pub fn toggle_y_lock(&mut self) {
+        self.y_range = match self.y_range {
+            Some(_) => None,
+            None => self.drawn.get().map(|drawn| drawn.y),
+        };
+    }
+
+    /// Fit the y-range to the visible points again (e.g. when the y-scale changes)
+    pub fn unlock_y(&mut self) {
+        self.y_range = None;
+    }
+
+    /// Note on the view for the chart title, e.g. "zoom ×4 · y locked"
+    fn note(&self, data_x: (f64, f64)) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some((lo, hi)) = self.x_range {
+            parts.push(format!("zoom ×{:.0}", (data_x.1 - data_x.0) / (hi - lo)));
+        }
+        if self.y_range.is_some() {
+            parts.push("y locked".to_string());
+        }
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+/// Index of the value in sorted `xs` nearest to `x`
+fn nearest_index(xs: &[f64], x: f64) -> Option<usize> {
+    (0..xs.len()).min_by(|&a, &b| (xs[a] - x).abs().total_cmp(&(xs[b] - x).abs()))
+}
+
+/// Move `range` to lie within `bounds`, keeping its width
+fn clamp_range(range: (f64, f64), bounds: (f64, f64)) -> (f64, f64) {
+    let width = range.1 - range.0;
+    if range.0 < bounds.0 {
+        (bounds.0, bounds.0 + width)
+    } else if range.1 > bounds.1 {
+        (bounds.1 - width, bounds.1)
+    } else {
+        range
+    }
+}
+
 /// Metrics chart widget for displaying line plots
 pub struct MetricsChart<'a> {
     metrics: &'a [RunMetric<'a>],
     title: &'a str,
+    /// Whether the focused run's metrics are still being loaded
+    loading: bool,
+    resume_mode: ResumeMode,
+    x_axis: XAxis,
+    /// Axis data of each shown run, by run index
+    run_axes: HashMap<usize, RunAxis<'a>>,
+    timezone: DisplayTimezone,
+    y_scale: YScale,
+    smoothing: Smoothing,
+    view: Option<&'a ChartView>,
+    focused: bool,
+    /// Run the cursor readout shows deltas against
+    focused_run: Option<usize>,
+    /// Display formats by metric name pattern
+    formats: &'a [MetricFormat],
 }
 
 impl<'a> MetricsChart<'a> {
     pub fn new(metrics: &'a [RunMetric<'a>], title: &'a str) -> Self {
-        MetricsChart { metrics, title }
+        MetricsChart {
+            metrics,
+            title,
+            loading: false,
+            resume_mode: ResumeMode::default(),
+            x_axis: XAxis::default(),
+            run_axes: HashMap::new(),
+            timezone: DisplayTimezone::default(),
+            y_scale: YScale::default(),
+            smoothing: Smoothing::default(),
+            view: None,
+            focused: false,
+            focused_run: None,
+            formats: &[],
+        }
+    }
+
+    /// Set the display formats of metrics, by name pattern
+    pub fn formats(mut self, formats: &'a [MetricFormat]) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Set the run whose values the cursor readout compares others with
+    pub fn focused_run(mut self, run_idx: usize) -> Self {
+        self.focused_run = Some(run_idx);
+        self
+    }
+
+    /// Show the range of `view`, and record the drawn bounds in it
+    pub fn view(mut self, view: &'a ChartView) -> Self {
+        self.view = Some(view);
+        self
+    }
+
+    /// Highlight the chart as focused (zoom and pan keys apply to it)
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Set the smoothing of all lines
+    pub fn smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Set the y-axis scale
+    pub fn y_scale(mut self, y_scale: YScale) -> Self {
+        self.y_scale = y_scale;
+        self
+    }
+
+    /// Set the time zone of wall-clock time labels
+    pub fn timezone(mut self, timezone: DisplayTimezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Mark the focused run's data as still loading
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    /// Set how resumed runs are drawn
+    pub fn resume_mode(mut self, resume_mode: ResumeMode) -> Self {
+        self.resume_mode = resume_mode;
+        self
+    }
+
+    /// Set the x-axis source, with the axis data of each run by run index
+    pub fn x_axis(mut self, x_axis: XAxis, run_axes: HashMap<usize, RunAxis<'a>>) -> Self {
+        self.x_axis = x_axis;
+        self.run_axes = run_axes;
+        self
+    }
+
+    /// Chart coordinates of points of the run at `run_idx`
+    fn coordinates(&self, run_idx: usize, points: &[&MetricPoint]) -> Vec<(f64, f64)> {
+        let run = self.run_axes.get(&run_idx).cloned().unwrap_or_default();
+        coordinates(points, &self.x_axis, &run)
+    }
+
+    /// Lines drawn for a metric under the resume mode
+    fn metric_lines(&self, metric: &'a Metric) -> Vec<Vec<&'a MetricPoint>> {
+        match self.resume_mode {
+            ResumeMode::Latest => vec![metric.latest_writes()],
+            ResumeMode::Segments => metric.write_segments(),
+            ResumeMode::All => vec![metric.points().iter().collect()],
+        }
+    }
+
+    /// Smoothed copy of a line. Smoothing is costly on long runs and charts are
+    /// redrawn many times a second, so results are kept in the view and reused
+    /// until the line's points or the smoothing change.
+    fn smoothed(&self, key: &LineKey, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let Some(view) = self.view else {
+            return self.smoothing.apply(points);
+        };
+        let mut cache = view.smoothed.borrow_mut();
+        if let Some(line) = cache.get(key) {
+            if line.smoothing == self.smoothing && same_points(&line.points, points) {
+                return line.smoothed.clone();
+            }
+        }
+        let smoothed = self.smoothing.apply(points);
+        cache.insert(
+            key.clone(),
+            SmoothedLine {
+                smoothing: self.smoothing,
+                points: points.to_vec(),
+                smoothed: smoothed.clone(),
+            },
+        );
+        smoothed
+    }
+
+    /// Chart block title, with the smoothing and a loading indicator if needed
+    fn block_title(&self) -> String {
+        let mut title = format!(" {}", self.title);
+        if let Some(smoothing) = self.smoothing.label() {
+            title.push_str(&format!(" · {smoothing}"));
+        }
+        if self.loading {
+            title.push_str(" (loading…)");
+        }
+        title.push(' ');
+        title
+    }
+
+    /// Format of the y-axis labels: that of the shown metrics if they share
+    /// one, `Auto` otherwise
+    fn y_format(&self) -> ValueFormat {
+        let mut formats = self
+            .metrics
+            .iter()
+            .map(|(_, _, _, metric)| MetricFormat::find(self.formats, &metric.name));
+        let first = formats.next().unwrap_or_default();
+        if formats.all(|format| format == first) {
+            first
+        } else {
+            ValueFormat::Auto
+        }
+    }
+
+    /// Format of the x-axis labels
+    fn x_format(&self) -> ValueFormat {
+        match &self.x_axis {
+            XAxis::Metric(name) => MetricFormat::find(self.formats, name),
+            _ => ValueFormat::Auto,
+        }
+    }
+
+    /// Readout rows at cursor position `x`: run, metric, value and delta to the
+    /// focused run. Values between logged steps are interpolated (marked "~").
+    fn readout(&self, x: f64) -> Vec<[String; 4]> {
+        let values: Vec<Option<(f64, bool)>> = self
+            .metrics
+            .iter()
+            .map(|(_, run_idx, _, metric)| {
+                value_at(&self.coordinates(*run_idx, &metric.latest_writes()), x)
+            })
+            .collect();
+        self.metrics
+            .iter()
+            .zip(&values)
+            .map(|((run_name, run_idx, metric_idx, metric), value)| {
+                let format = MetricFormat::find(self.formats, &metric.name);
+                let focused_value = self
+                    .metrics
+                    .iter()
+                    .zip(&values)
+                    .find(|((_, r, m, _), _)| Some(*r) == self.focused_run && m == metric_idx)
+                    .and_then(|(_, value)| *value);
+                let delta = match (value, focused_value) {
+                    (Some((v, _)), Some((f, _))) if Some(*run_idx) != self.focused_run => {
+                        let delta = v - f;
+                        let sign = if delta >= 0.0 { "+" } else { "" };
+                        format!("{sign}{}", format_readout(delta, format))
+                    }
+                    _ => String::new(),
+                };
+                let value = match value {
+                    Some((v, true)) => format_readout(*v, format),
+                    Some((v, false)) => format!("~{}", format_readout(*v, format)),
+                    None => "—".to_string(),
+                };
+                [run_name.clone(), metric.name.clone(), value, delta]
+            })
+            .collect()
+    }
+
+    /// Note on the non-finite values of the shown series, e.g. "⚠ 3 non-finite from step 120"
+    fn non_finite_note(&self) -> Option<String> {
+        let steps: Vec<i64> = self
+            .metrics
+            .iter()
+            .flat_map(|(_, _, _, metric)| metric.points().iter())
+            .filter(|p| !p.value.is_finite())
+            .map(|p| p.step)
+            .collect();
+        let first = steps.iter().min()?;
+        Some(format!("⚠ {} non-finite from step {first}", steps.len()))
+    }
+
+    /// Note on where the shown runs were resumed, e.g. "↺ resumed at step 120, 300 · latest"
+    fn resume_note(&self, resume_steps: &[i64]) -> Option<String> {
+        if resume_steps.is_empty() {
+            return None;
+        }
+        let steps: Vec<String> = resume_steps.iter().map(i64::to_string).collect();
+        Some(format!(
+            "↺ resumed at step {} · {}",
+            steps.join(", "),
+            self.resume_mode.label()
+        ))
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
@@ -50,21 +780,155 @@ impl<'a> MetricsChart<'a> {
             return;
         }
 
-        // Build points for each run
-        let chart_data: Vec<Vec<(f64, f64)>> = self
-            .metrics
+        // Build lines for each run; smoothed lines go last, to be drawn over
+        // the dimmed raw lines
+        let mut lines: Vec<ChartLine> = Vec::new();
+        let mut smoothed_lines: Vec<ChartLine> = Vec::new();
+        let mut resume_steps: Vec<i64> = Vec::new();
+        let mut resume_xs: Vec<f64> = Vec::new();
+        let mut hidden = 0;
+        let mut smoothed_keys: Vec<LineKey> = Vec::new();
+        for (run_name, run_idx, metric_idx, metric) in self.metrics.iter() {
+            let metric_lines = self.metric_lines(metric);
+            let count = metric_lines.len();
+            for (i, points) in metric_lines.into_iter().enumerate() {
+                let points = self.coordinates(*run_idx, &points);
+                let scale = |points: &[(f64, f64)]| -> Vec<(f64, f64)> {
+                    points
+                        .iter()
+                        .filter_map(|&(x, y)| Some((x, self.y_scale.transform(y)?)))
+                        .collect()
+                };
+                let scaled = scale(&points);
+                hidden += points.len() - scaled.len();
+                let older = i + 1 < count;
+                if self.smoothing.is_active() {
+                    let key = (run_name.clone(), metric.name.clone(), i);
+                    let smoothed = scale(&self.smoothed(&key, &points));
+                    smoothed_keys.push(key);
+                    smoothed_lines.push((*run_idx, *metric_idx, older, smoothed));
+                    lines.push((*run_idx, *metric_idx, true, scaled));
+                } else {
+                    lines.push((*run_idx, *metric_idx, older, scaled));
+                }
+            }
+            for segment in metric.write_segments().iter().skip(1) {
+                resume_steps.push(segment[0].step);
+                let first = self.coordinates(*run_idx, &segment[..1]);
+                resume_xs.extend(first.iter().map(|&(x, _)| x));
+            }
+        }
+        lines.append(&mut smoothed_lines);
+        if let Some(view) = self.view {
+            // Only keep the lines still shown
+            view.smoothed
+                .borrow_mut()
+                .retain(|key, _| smoothed_keys.contains(key));
+        }
+        resume_steps.sort_unstable();
+        resume_steps.dedup();
+        let all_points: Vec<Vec<(f64, f64)>> = lines
             .iter()
-            .map(|(_, _, _, metric)| {
-                metric
-                    .points
+            .map(|(_, _, _, points)| points.clone())
+            .collect();
+
+        // Calculate bounds from all points (non-finite values only widen the steps),
+        // then narrow them to the view: y fits the points in the visible x-range
+        // unless locked
+        let (data_x, data_y) = calculate_bounds(&all_points);
+        let x_range = self.view.and_then(|view| view.x_range);
+        let x_bounds = x_range.unwrap_or(data_x);
+        let y_bounds = match (self.view.and_then(|view| view.y_range), x_range) {
+            (Some(y_range), _) => y_range,
+            (None, Some((lo, hi))) => {
+                let visible: Vec<Vec<(f64, f64)>> = all_points
                     .iter()
-                    .map(|p| (p.step as f64, p.value))
-                    .collect()
+                    .map(|points| {
+                        points
+                            .iter()
+                            .copied()
+                            .filter(|&(x, y)| x >= lo && x <= hi && y.is_finite())
+                            .collect()
+                    })
+                    .collect();
+                if visible.iter().all(Vec::is_empty) {
+                    data_y
+                } else {
+                    calculate_bounds(&visible).1
+                }
+            }
+            (None, None) => data_y,
+        };
+
+        // Ticks at round numbers, which widen an axis fitting the data to
+        // enclose them; a zoomed or locked range keeps its bounds, with evenly
+        // spaced ticks
+        let max_y_ticks = (area.height.saturating_sub(4) / Y_TICK_SPACING).clamp(2, MAX_TICKS);
+        let y_ticks = if self.view.is_some_and(|view| view.y_range.is_some()) {
+            axis::even_ticks(y_bounds.0, y_bounds.1, max_y_ticks as usize)
+        } else {
+            // Log scales tick whole decades when they span any
+            let min_step = if self.y_scale != YScale::Linear && y_bounds.1 - y_bounds.0 >= 1.0 {
+                1.0
+            } else {
+                0.0
+            };
+            axis::nice_ticks(y_bounds.0, y_bounds.1, max_y_ticks as usize, min_step)
+        };
+        let y_bounds = (y_ticks[0], y_ticks[y_ticks.len() - 1]);
+        let max_x_ticks = (area.width / X_TICK_SPACING).clamp(2, MAX_TICKS) as usize;
+        let x_ticks = match (&self.x_axis, x_range) {
+            (XAxis::Step, None) => axis::nice_ticks(x_bounds.0, x_bounds.1, max_x_ticks, 1.0),
+            (XAxis::Metric(_), None) => axis::nice_ticks(x_bounds.0, x_bounds.1, max_x_ticks, 0.0),
+            (XAxis::Step | XAxis::Metric(_), Some(_)) => {
+                axis::even_ticks(x_bounds.0, x_bounds.1, max_x_ticks)
+            }
+            // Times have longer labels
+            _ => axis::even_ticks(
+                x_bounds.0,
+                x_bounds.1,
+                (area.width / TIME_TICK_SPACING).clamp(2, MAX_TICKS) as usize,
+            ),
+        };
+        let x_bounds = (x_ticks[0], x_ticks[x_ticks.len() - 1]);
+        if let Some(view) = self.view {
+            let mut xs: Vec<f64> = all_points.iter().flatten().map(|&(x, _)| x).collect();
+            xs.sort_by(f64::total_cmp);
+            xs.dedup();
+            *view.drawn_xs.borrow_mut() = xs;
+        }
+        let cursor = self.view.and_then(|view| view.cursor);
+
+        // Lines skip non-finite values, which are marked on the top edge
+        // (NaN, +inf) or bottom edge (-inf) of the chart instead
+        for (_, _, _, points) in &mut lines {
+            points.retain(|(_, y)| y.is_finite());
+        }
+        let non_finite_marks: Vec<(f64, f64)> = all_points
+            .iter()
+            .flatten()
+            .filter(|(_, y)| !y.is_finite())
+            .map(|&(x, y)| {
+                if y == f64::NEG_INFINITY {
+                    (x, y_bounds.0)
+                } else {
+                    (x, y_bounds.1)
+                }
             })
             .collect();
-
-        // Calculate bounds from all points
-        let (x_bounds, y_bounds) = calculate_bounds(&chart_data);
+        let resume_marks: Vec<[(f64, f64); 2]> = resume_xs
+            .iter()
+            .map(|&x| [(x, y_bounds.0), (x, y_bounds.1)])
+            .collect();
+        // Cursor line, and a dimmed line where a mouse drag started
+        let cursor_mark: Vec<(f64, f64)> = cursor
+            .map(|x| vec![(x, y_bounds.0), (x, y_bounds.1)])
+            .unwrap_or_default();
+        let drag_mark: Vec<(f64, f64)> = self
+            .view
+            .and_then(|view| view.drag_start)
+            .map(|x| vec![(x, y_bounds.0), (x, y_bounds.1)])
+            .unwrap_or_default();
 
         // Collect unique runs and metrics for factorized legend
         let mut unique_runs: Vec<(String, usize)> = Vec::new();
@@ -84,23 +948,67 @@ impl<'a> MetricsChart<'a> {
         let multi_run = unique_runs.len() > 1;
         let multi_metric = unique_metrics.len() > 1;
 
-        // Create datasets (no legend names - we use factorized legend)
-        let datasets: Vec<Dataset> = self
-            .metrics
+        // Resume markers first, so lines are drawn over them
+        let mut datasets: Vec<Dataset> = resume_marks
             .iter()
-            .zip(chart_data.iter())
-            .map(|((_, run_idx, metric_idx, _), points)| {
-                let color = COLORS[*run_idx % COLORS.len()];
-                let marker = MARKERS[*metric_idx % MARKERS.len()];
+            .map(|mark| {
                 Dataset::default()
-                    .name("") // Empty name - we use custom legend
-                    .marker(marker)
+                    .marker(Marker::Braille)
                     .graph_type(GraphType::Line)
-                    .style(Style::default().fg(color))
-                    .data(points)
+                    .style(Style::default().fg(RESUME_COLOR))
+                    .data(mark)
             })
             .collect();
 
+        // Create datasets (no legend names - we use factorized legend);
+        // raw lines under smoothed ones and segments written before a resume are dimmed
+        datasets.extend(lines.iter().map(|(run_idx, metric_idx, older, points)| {
+            let color = COLORS[*run_idx % COLORS.len()];
+            let marker = MARKERS[*metric_idx % MARKERS.len()];
+            let style = if *older {
+                Style::default().fg(color).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(color)
+            };
+            Dataset::default()
+                .name("") // Empty name - we use custom legend
+                .marker(marker)
+                .graph_type(GraphType::Line)
+                .style(style)
+                .data(points)
+        }));
+        if !cursor_mark.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(CURSOR_COLOR))
+                    .data(&cursor_mark),
+            );
+        }
+        if !drag_mark.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .marker(Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(
+                        Style::default()
+                            .fg(CURSOR_COLOR)
+                            .add_modifier(Modifier::DIM),
+                    )
+                    .data(&drag_mark),
+            );
+        }
+        if !non_finite_marks.is_empty() {
+            datasets.push(
+                Dataset::default()
+                    .marker(Marker::Block)
+                    .graph_type(GraphType::Scatter)
+                    .style(Style::default().fg(NON_FINITE_COLOR))
+                    .data(&non_finite_marks),
+            );
+        }
+
         // Split area: optional legend row + chart
         let show_legend = multi_run || multi_metric;
         let (legend_area, chart_area) = if show_legend {
@@ -119,34 +1027,124 @@ impl<'a> MetricsChart<'a> {
             frame.render_widget(legend, legend_rect);
         }
 
+        // Readout of the values at the cursor below the chart
+        let chart_area = match cursor {
+            Some(x) => {
+                let rows = self.readout(x);
+                let height = (rows.len() as u16 + 3).min(chart_area.height / 2);
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(5), Constraint::Length(height)])
+                    .split(chart_area);
+                let label = self.x_axis.label(
+                    x,
+                    x_bounds.1 - x_bounds.0,
+                    x.abs() * READOUT_PRECISION,
+                    self.x_format(),
+                    self.timezone,
+                );
+                self.render_readout(frame, chunks[1], &label, rows);
+                chunks[0]
+            }
+            None => chart_area,
+        };
+
+        let mut block = Block::default()
+            .title(self.block_title())
+            .borders(Borders::ALL);
+        if self.focused {
+            block = block
+                .border_type(BorderType::Double)
+                .border_style(Style::default().fg(Color::Cyan));
+        }
+        if let Some(note) = self.view.and_then(|view| view.note(data_x)) {
+            block = block.title(Line::from(format!(" {note} ")).right_aligned());
+        }
+        if let Some(note) = self.resume_note(&resume_steps) {
+            block = block.title(
+                Line::from(format!(" {note} "))
+                    .style(Style::default().fg(RESUME_COLOR))
+                    .right_aligned(),
+            );
+        }
+        if hidden > 0 {
+            block = block.title(
+                Line::from(format!(" {hidden} values ≤ 0 hidden on log scale "))
+                    .style(Style::default().add_modifier(Modifier::DIM))
+                    .right_aligned(),
+            );
+        }
+        if let Some(note) = self.non_finite_note() {
+            block = block.title(
+                Line::from(format!(" {note} "))
+                    .style(Style::default().fg(NON_FINITE_COLOR))
+                    .right_aligned(),
+            );
+        }
+        let span = x_bounds.1 - x_bounds.0;
+        let x_precision = axis::tick_precision(&x_ticks);
+        let x_format = self.x_format();
+        let x_labels: Vec<String> = x_ticks
+            .iter()
+            .map(|&x| {
+                self.x_axis
+                    .label(x, span, x_precision, x_format, self.timezone)
+            })
+            .collect();
+        let y_labels = self.y_scale.labels(&y_ticks, self.y_format());
+        if let Some(view) = self.view {
+            view.drawn.set(Some(DrawnBounds {
+                data_x,
+                x: x_bounds,
+                y: y_bounds,
+                plot: plot_area(block.inner(chart_area), &x_labels, &y_labels),
+            }));
+        }
         let chart = Chart::new(datasets)
-            .block(
-                Block::default()
-                    .title(format!(" {} ", self.title))
-                    .borders(Borders::ALL),
-            )
+            .block(block)
             .x_axis(
                 Axis::default()
-                    .title("step")
+                    .title(self.x_axis.title())
                     .bounds([x_bounds.0, x_bounds.1])
-                    .labels(vec![
-                        format!("{:.0}", x_bounds.0),
-                        format!("{:.0}", x_bounds.1),
-                    ]),
+                    .labels(x_labels),
             )
             .y_axis(
                 Axis::default()
+                    .title(self.y_scale.name())
                     .bounds([y_bounds.0, y_bounds.1])
-                    .labels(vec![
-                        format!("{:.2}", y_bounds.0),
-                        format!("{:.2}", y_bounds.1),
-                    ]),
+                    .labels(y_labels),
             )
             .legend_position(None); // Disabled - using custom factorized legend
 
         frame.render_widget(chart, chart_area);
     }
 
+    /// Table of the values at the cursor, titled with its position
+    fn render_readout(&self, frame: &mut Frame, area: Rect, label: &str, rows: Vec<[String; 4]>) {
+        let title = format!(" {} {label} ", self.x_axis.title());
+        let header = Row::new(["run", "metric", "value", "Δ focused"]).style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        let widths = [
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ];
+        let body = rows
+            .into_iter()
+            .map(|row| Row::new(row.map(TableCell::from)));
+        let table = Table::new(body, widths).header(header).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(CURSOR_COLOR)),
+        );
+        frame.render_widget(table, area);
+    }
+
     /// Build a factorized legend showing runs by color and metrics by marker
     fn build_factorized_legend<'b>(
         &self,
@@ -199,22 +1197,67 @@ impl<'a> MetricsChart<'a> {
 
     fn render_empty(&self, frame: &mut Frame, area: Rect) {
         let block = Block::default()
-            .title(format!(" {} ", self.title))
+            .title(self.block_title())
             .borders(Borders::ALL);
 
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        let message = ratatui::widgets::Paragraph::new("No data available")
-            .alignment(ratatui::layout::Alignment::Center);
+        let text = if self.loading {
+            "Loading…"
+        } else {
+            "No data available"
+        };
+        let message =
+            ratatui::widgets::Paragraph::new(text).alignment(ratatui::layout::Alignment::Center);
 
         frame.render_widget(message, inner);
     }
 }
 
+/// Cells of `area` that ratatui's `Chart` draws lines in, given the axis labels:
+/// right of the y labels and axis, above the x axis and labels
+fn plot_area(area: Rect, x_labels: &[String], y_labels: &[String]) -> Rect {
+    let width = |label: &String| label.chars().count() as u16;
+    let y_label_width = y_labels.iter().map(width).max().unwrap_or(0);
+    let first_x_label = x_labels.first().map(width).unwrap_or(0);
+    let left = y_label_width
+        .max(first_x_label.saturating_sub(1))
+        .min(area.width / 3)
+        + 1;
+    Rect::new(
+        area.x + left,
+        area.y,
+        area.width.saturating_sub(left),
+        area.height.saturating_sub(2),
+    )
+}
+
+/// Value of a line at `x`: the logged value there (flagged `true`), or one
+/// interpolated between the nearest finite values on each side
+fn value_at(points: &[(f64, f64)], x: f64) -> Option<(f64, bool)> {
+    if let Some(&(_, y)) = points.iter().rev().find(|&&(px, _)| px == x) {
+        return Some((y, true));
+    }
+    let finite = || points.iter().filter(|(_, y)| y.is_finite());
+    let (x0, y0) = finite()
+        .filter(|&&(px, _)| px < x)
+        .max_by(|a, b| a.0.total_cmp(&b.0))?;
+    let (x1, y1) = finite()
+        .filter(|&&(px, _)| px > x)
+        .min_by(|a, b| a.0.total_cmp(&b.0))?;
+    Some((y0 + (y1 - y0) * (x - x0) / (x1 - x0), false))
+}
+
+/// Readout text of a value in `format`, to four significant digits
+fn format_readout(value: f64, format: ValueFormat) -> String {
+    axis::format_value(value, value.abs() * READOUT_PRECISION, format)
+}
+
 /// Calculate X and Y bounds from chart data.
+/// Non-finite values count towards the X bounds only.
 /// Returns default bounds (0,1) for each axis if data is empty.
-fn calculate_bounds(data: &[Vec<(f64, f64)>]) -> ((f64, f64), (f64, f64)) {
+pub(super) fn calculate_bounds(data: &[Vec<(f64, f64)>]) -> ((f64, f64), (f64, f64)) {
     // Check if there's any data at all
     let has_data = data.iter().any(|points| !points.is_empty());
     if !has_data {
@@ -230,11 +1273,18 @@ fn calculate_bounds(data: &[Vec<(f64, f64)>]) -> ((f64, f64), (f64, f64)) {
         for &(x, y) in points {
             x_min = x_min.min(x);
             x_max = x_max.max(x);
-            y_min = y_min.min(y);
-            y_max = y_max.max(y);
+            if y.is_finite() {
+                y_min = y_min.min(y);
+                y_max = y_max.max(y);
+            }
         }
     }
 
+    // Only non-finite values
+    if y_min > y_max {
+        (y_min, y_max) = (0.0, 1.0);
+    }
+
     // Ensure valid bounds (for single-point case)
     if x_min >= x_max {
         x_max = x_min + 1.0;
@@ -274,6 +1324,379 @@ mod tests {
         assert!(non_empty, "Chart should render something");
     }
 
+    #[test]
+    fn test_resume_marked_in_title() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let metric = Metric::with_points(
+            "loss".to_string(),
+            [(0, 0), (1, 1), (1, 3), (2, 2)]
+                .iter()
+                .map(|&(step, secs)| MetricPoint {
+                    step,
+                    value: 1.0 / (step + 1) as f64,
+                    timestamp: Some(start + chrono::Duration::seconds(secs)),
+                })
+                .collect(),
+        );
+        let metrics = vec![("run".to_string(), 0, 0, &metric)];
+        let mut terminal = Terminal::new(TestBackend::new(80, 15)).unwrap();
+        terminal
+            .draw(|frame| {
+                MetricsChart::new(&metrics, "loss")
+                    .resume_mode(ResumeMode::Segments)
+                    .render(frame, frame.area())
+            })
+            .unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(rendered.contains("resumed at step 1 · segments"));
+    }
+
+    #[test]
+    fn test_ticks_use_metric_format() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let metric = Metric::with_points(
+            "train/acc".to_string(),
+            (0..=190)
+                .map(|step| MetricPoint {
+                    step,
+                    value: 0.13 + step as f64 * 0.004,
+                    timestamp: None,
+                })
+                .collect(),
+        );
+        let metrics = vec![("run".to_string(), 0, 0, &metric)];
+        let formats = [MetricFormat {
+            pattern: "*acc".to_string(),
+            format: ValueFormat::Percent,
+        }];
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| {
+                MetricsChart::new(&metrics, "acc")
+                    .formats(&formats)
+                    .render(frame, frame.area())
+            })
+            .unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        // Round intermediate ticks on both axes, enclosing the data
+        for label in ["0%", "25%", "75%", "100%", "50", "150", "200"] {
+            assert!(rendered.contains(label), "missing tick {label}");
+        }
+    }
+
+    #[test]
+    fn test_smoothing_in_title() {
+        use super::super::smoothing::SmoothingMode;
+
+        let metric = Metric::with_points(
+            "loss".to_string(),
+            (0..20)
+                .map(|step| MetricPoint {
+                    step,
+                    value: (step % 2) as f64,
+                    timestamp: None,
+                })
+                .collect(),
+        );
+        let metrics = vec![("run".to_string(), 0, 0, &metric)];
+        let chart = MetricsChart::new(&metrics, "loss").smoothing(Smoothing {
+            mode: SmoothingMode::Ema,
+            strength: 0.6,
+        });
+        assert_eq!(chart.block_title(), " loss · EMA 0.60 ");
+        assert_eq!(MetricsChart::new(&metrics, "loss").block_title(), " loss ");
+    }
+
+    #[test]
+    fn test_smoothed_lines_are_cached() {
+        use super::super::smoothing::SmoothingMode;
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let view = ChartView::new();
+        let metric = |values: &[f64]| {
+            Metric::with_points(
+                "loss".to_string(),
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(step, &value)| MetricPoint {
+                        step: step as i64,
+                        value,
+                        timestamp: None,
+                    })
+                    .collect(),
+            )
+        };
+        let draw = |metric: &Metric, strength: f64| {
+            let metrics = vec![("run".to_string(), 0, 0, metric)];
+            let chart = MetricsChart::new(&metrics, "loss")
+                .view(&view)
+                .smoothing(Smoothing {
+                    mode: SmoothingMode::Median,
+                    strength,
+                });
+            let mut terminal = Terminal::new(TestBackend::new(60, 15)).unwrap();
+            terminal
+                .draw(|frame| chart.render(frame, frame.area()))
+                .unwrap();
+        };
+        let cached = || {
+            let key = ("run".to_string(), "loss".to_string(), 0);
+            view.smoothed
+                .borrow()
+                .get(&key)
+                .map(|line| line.smoothed.iter().map(|&(_, y)| y).collect::<Vec<_>>())
+        };
+
+        draw(&metric(&[1.0, 9.0, 3.0]), 0.02);
+        assert_eq!(cached(), Some(vec![5.0, 3.0, 6.0]));
+        // Recomputed when the points or the strength change
+        draw(&metric(&[1.0, 9.0, 3.0, 4.0]), 0.02);
+        assert_eq!(cached(), Some(vec![5.0, 3.0, 4.0, 3.5]));
+        draw(&metric(&[1.0, 9.0, 3.0, 4.0]), 0.0);
+        assert_eq!(cached(), None);
+    }
+
+    #[test]
+    fn test_chart_view_zoom_and_pan() {
+        let mut view = ChartView::new();
+        let draw = |view: &ChartView| {
+            view.drawn.set(Some(DrawnBounds {
+                data_x: (0.0, 100.0),
+                x: view.x_range.unwrap_or((0.0, 100.0)),
+                y: (1.0, 2.0),
+                plot: Rect::new(0, 0, 101, 10),
+            }))
+        };
+
+        draw(&view);
+        view.pan(1.0);
+        assert_eq!(view.x_range, None);
+        view.zoom(true);
+        assert_eq!(view.x_range, Some((25.0, 75.0)));
+        draw(&view);
+        view.pan(1.0);
+        assert_eq!(view.x_range, Some((37.5, 87.5)));
+        draw(&view);
+        view.pan(1.0);
+        assert_eq!(view.x_range, Some((50.0, 100.0)));
+        assert_eq!(view.note((0.0, 100.0)).as_deref(), Some("zoom ×2"));
+
+        view.toggle_y_lock();
+        assert_eq!(view.y_range, Some((1.0, 2.0)));
+        draw(&view);
+        view.zoom(false);
+        assert_eq!(view.x_range, None);
+        assert_eq!(view.note((0.0, 100.0)).as_deref(), Some("y locked"));
+        view.reset();
+        assert_eq!(view.note((0.0, 100.0)), None);
+    }
+
+    #[test]
+    fn test_cursor_readout() {
+        let line = [(0.0, 1.0), (10.0, 3.0), (20.0, f64::NAN)];
+        assert_eq!(value_at(&line, 10.0), Some((3.0, true)));
+        assert_eq!(value_at(&line, 5.0), Some((2.0, false)));
+        assert_eq!(value_at(&line, 15.0), None);
+        assert!(value_at(&line, 20.0).unwrap().0.is_nan());
+
+        let metric = |name: &str, values: &[f64]| {
+            Metric::with_points(
+                name.to_string(),
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &value)| MetricPoint {
+                        step: i as i64 * 10,
+                        value,
+                        timestamp: None,
+                    })
+                    .collect(),
+            )
+        };
+        let (focused, other) = (metric("loss", &[1.0, 0.5]), metric("loss", &[2.0, 1.0]));
+        let metrics = vec![
+            ("other".to_string(), 1, 0, &other),
+            ("focused".to_string(), 0, 0, &focused),
+        ];
+        let chart = MetricsChart::new(&metrics, "loss").focused_run(0);
+        assert_eq!(
+            chart.readout(5.0),
+            vec![
+                ["other", "loss", "~1.500", "+0.7500"].map(String::from),
+                ["focused", "loss", "~0.7500", ""].map(String::from),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cursor_moves_between_logged_steps() {
+        let mut view = ChartView::new();
+        *view.drawn_xs.borrow_mut() = vec![0.0, 10.0, 20.0, 30.0];
+        view.drawn.set(Some(DrawnBounds {
+            data_x: (0.0, 30.0),
+            x: (20.0, 30.0),
+            y: (0.0, 1.0),
+            plot: Rect::new(0, 0, 11, 10),
+        }));
+        view.x_range = Some((20.0, 30.0));
+
+        view.toggle_cursor();
+        assert_eq!(view.cursor, Some(30.0));
+        view.move_cursor(5);
+        assert_eq!(view.cursor, Some(30.0));
+        // Leaving the visible range pans to follow
+        view.move_cursor(-2);
+        assert_eq!(view.cursor, Some(10.0));
+        assert_eq!(view.x_range, Some((10.0, 20.0)));
+        view.move_cursor(-5);
+        assert_eq!(view.cursor, Some(0.0));
+        view.toggle_cursor();
+        assert_eq!(view.cursor, None);
+    }
+
+    #[test]
+    fn test_mouse_positions_on_plot() {
+        // Labels "0.50"/"1.00" on the left, then the y axis
+        let plot = plot_area(
+            Rect::new(10, 5, 60, 20),
+            &["0".to_string(), "100".to_string()],
+            &["0.50".to_string(), "1.00".to_string()],
+        );
+        assert_eq!(plot, Rect::new(15, 5, 55, 18));
+
+        let mut view = ChartView::new();
+        *view.drawn_xs.borrow_mut() = vec![0.0, 50.0, 100.0];
+        view.drawn.set(Some(DrawnBounds {
+            data_x: (0.0, 100.0),
+            x: (0.0, 100.0),
+            y: (0.0, 1.0),
+            plot: Rect::new(0, 0, 101, 10),
+        }));
+        assert_eq!(view.x_at(40, 3), Some(40.0));
+        assert_eq!(view.x_at(40, 10), None);
+
+        // Hovering only moves a shown cursor
+        view.hover(40, 3);
+        assert_eq!(view.cursor, None);
+        view.cursor = Some(0.0);
+        view.hover(40, 3);
+        assert_eq!(view.cursor, Some(50.0));
+
+        // Clicks don't zoom; drags do, also when released past the plot
+        assert!(view.start_drag(30, 3));
+        view.end_drag(30);
+        assert_eq!(view.x_range, None);
+        assert!(view.start_drag(30, 3));
+        view.end_drag(200);
+        assert_eq!(view.x_range, Some((30.0, 100.0)));
+    }
+
+    #[test]
+    fn test_coordinates_on_each_x_axis() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let point = |step: i64, value: f64| MetricPoint {
+            step,
+            value,
+            timestamp: Some(start + chrono::Duration::seconds(step * 10)),
+        };
+        let loss = [point(1, 0.5), point(2, 0.25), point(3, 0.125)];
+        let points: Vec<&MetricPoint> = loss.iter().collect();
+        let epoch = Metric::with_points(
+            "epoch".to_string(),
+            vec![point(0, 0.0), point(2, 1.0), point(3, f64::NAN)],
+        );
+        let run = RunAxis {
+            start: Some(start),
+            x_metric: Some(&epoch),
+        };
+
+        assert_eq!(
+            coordinates(&points, &XAxis::Step, &run),
+            vec![(1.0, 0.5), (2.0, 0.25), (3.0, 0.125)]
+        );
+        assert_eq!(
+            coordinates(&points, &XAxis::RelativeTime, &run)[2],
+            (30.0, 0.125)
+        );
+        assert_eq!(
+            coordinates(&points, &XAxis::WallTime, &run)[0].0,
+            1_700_000_010.0
+        );
+        // Only steps with a finite x value are kept
+        assert_eq!(
+            coordinates(&points, &XAxis::Metric("epoch".to_string()), &run),
+            vec![(1.0, 0.25)]
+        );
+        assert!(coordinates(&points, &XAxis::RelativeTime, &RunAxis::default()).is_empty());
+    }
+
+    #[test]
+    fn test_time_labels() {
+        assert_eq!(format_duration(45.0), "45s");
+        assert_eq!(format_duration(750.0), "12m30s");
+        assert_eq!(format_duration(600.0), "10m");
+        assert_eq!(format_duration(4800.0), "1h20m");
+        assert_eq!(format_duration(7200.0), "2h");
+        assert_eq!(format_duration(183600.0), "2d3h");
+
+        let utc = DisplayTimezone::Fixed(chrono::FixedOffset::east_opt(0).unwrap());
+        let plus_two = DisplayTimezone::Fixed(chrono::FixedOffset::east_opt(7200).unwrap());
+        let x = 1_700_000_000.0; // 2023-11-14 22:13:20 UTC
+        assert_eq!(
+            XAxis::WallTime.label(x, 3600.0, 0.0, ValueFormat::Auto, utc),
+            "22:13"
+        );
+        assert_eq!(
+            XAxis::WallTime.label(x, 60.0, 0.0, ValueFormat::Auto, plus_two),
+            "00:13:20"
+        );
+        assert_eq!(
+            XAxis::WallTime.label(x, 1e6, 0.0, ValueFormat::Auto, plus_two),
+            "11-15 00:13"
+        );
+    }
+
+    #[test]
+    fn test_y_scales() {
+        assert_eq!(YScale::Log.transform(100.0), Some(2.0));
+        assert_eq!(YScale::Log.transform(0.0), None);
+        assert!(YScale::Log.transform(f64::NAN).unwrap().is_nan());
+        assert_eq!(
+            YScale::Log.labels(&[-5.0, -4.0, -3.0], ValueFormat::Auto),
+            ["1e-5", "1e-4", "0.001"]
+        );
+        assert_eq!(
+            YScale::Linear.labels(&[0.0, 0.5, 1.0], ValueFormat::Percent),
+            ["0%", "50%", "100%"]
+        );
+        assert_eq!(
+            YScale::Linear.labels(&[0.0, 2e6, 4e6], ValueFormat::Auto),
+            ["0", "2M", "4M"]
+        );
+
+        // Symlog is symmetric and invertible through zero
+        let t = YScale::Symlog.transform(-99.0).unwrap();
+        assert!((t + 2.0).abs() < 1e-12);
+        assert!((YScale::Symlog.inverse(t) + 99.0).abs() < 1e-9);
+        assert_eq!(YScale::Symlog.transform(0.0), Some(0.0));
+    }
+
     #[test]
     fn test_calculate_bounds() {
         let data = vec![vec![(0.0, 1.0), (5.0, 3.0)], vec![(2.0, 0.5), (10.0, 2.0)]];
@@ -295,6 +1718,24 @@ mod tests {
         assert_eq!(y_bounds, (5.0, 6.0));
     }
 
+    #[test]
+    fn test_calculate_bounds_skips_non_finite() {
+        let data = vec![vec![
+            (0.0, 1.0),
+            (5.0, f64::NAN),
+            (8.0, f64::INFINITY),
+            (3.0, 2.0),
+        ]];
+        let (x_bounds, y_bounds) = calculate_bounds(&data);
+        assert_eq!(x_bounds, (0.0, 8.0));
+        assert_eq!(y_bounds, (1.0, 2.0));
+
+        let data = vec![vec![(0.0, f64::NAN), (4.0, f64::NEG_INFINITY)]];
+        let (x_bounds, y_bounds) = calculate_bounds(&data);
+        assert_eq!(x_bounds, (0.0, 4.0));
+        assert_eq!(y_bounds, (0.0, 1.0));
+    }
+
     #[test]
     fn test_calculate_bounds_empty_data() {
         // Completely empty
@@ -0,0 +1,295 @@
+//! Image encoders for the terminal: colored half-blocks and braille dots drawn
+//! as text, and kitty/sixel escape sequences written past the cell buffer.
+
+use std::io::Cursor;
+
+use base64::Engine;
+use image::{imageops::FilterType, ImageFormat, RgbaImage};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::cli::ImageProtocol;
+
+/// Pixel size of a terminal cell when the terminal doesn't report it
+const DEFAULT_CELL_SIZE: (u32, u32) = (8, 16);
+
+/// Size of base64 chunks in kitty graphics commands
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// Pixels with less opacity than this are left blank
+const MIN_ALPHA: u8 = 128;
+
+/// Pick a concrete protocol for `Auto` from the terminal's environment
+/// variables (`env` looks one up)
+pub fn detect_protocol(env: impl Fn(&str) -> Option<String>) -> ImageProtocol {
+    let term = env("TERM").unwrap_or_default();
+    let term_program = env("TERM_PROGRAM").unwrap_or_default();
+    let colorterm = env("COLORTERM").unwrap_or_default();
+
+    if env("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || matches!(term_program.as_str(), "WezTerm" | "ghostty")
+    {
+        ImageProtocol::Kitty
+    } else if term.contains("sixel") || term.starts_with("foot") || term_program == "mlterm" {
+        ImageProtocol::Sixel
+    } else if colorterm == "truecolor" || colorterm == "24bit" {
+        ImageProtocol::Halfblocks
+    } else {
+        ImageProtocol::Braille
+    }
+}
+
+/// Pixel size of one terminal cell
+pub fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns) as u32,
+            (size.height / size.rows) as u32,
+        ),
+        _ => DEFAULT_CELL_SIZE,
+    }
+}
+
+/// Largest size with the image's aspect ratio fitting in `max_width` x `max_height`
+pub fn fit(width: u32, height: u32, max_width: u32, max_height: u32) -> (u32, u32) {
+    if width == 0 || height == 0 {
+        return (0, 0);
+    }
+    let scale = f64::min(
+        max_width as f64 / width as f64,
+        max_height as f64 / height as f64,
+    );
+    (
+        ((width as f64 * scale) as u32).clamp(1, max_width.max(1)),
+        ((height as f64 * scale) as u32).clamp(1, max_height.max(1)),
+    )
+}
+
+/// Resize an image to fit `max_width` x `max_height` pixels
+pub fn resize_to_fit(image: &RgbaImage, max_width: u32, max_height: u32) -> RgbaImage {
+    let (width, height) = fit(image.width(), image.height(), max_width, max_height);
+    image::imageops::resize(image, width, height, FilterType::Triangle)
+}
+
+/// Draw an image in `cols` x `rows` cells with `▀`, two pixels per cell
+pub fn halfblock_lines(image: &RgbaImage, cols: u16, rows: u16) -> Vec<Line<'static>> {
+    let image = resize_to_fit(image, cols as u32, rows as u32 * 2);
+    let color = |x: u32, y: u32| {
+        if y >= image.height() {
+            return Color::Reset;
+        }
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        if a < MIN_ALPHA {
+            Color::Reset
+        } else {
+            Color::Rgb(r, g, b)
+        }
+    };
+
+    (0..image.height())
+        .step_by(2)
+        .map(|y| {
+            let cells: Vec<Span> = (0..image.width())
+                .map(|x| Span::styled("▀", Style::default().fg(color(x, y)).bg(color(x, y + 1))))
+                .collect();
+            Line::from(cells)
+        })
+        .collect()
+}
+
+/// Draw an image in `cols` x `rows` cells with braille dots, 2x4 dots per cell.
+/// Dots are set where a pixel is brighter than the image's average.
+pub fn braille_lines(image: &RgbaImage, cols: u16, rows: u16) -> Vec<Line<'static>> {
+    let image = resize_to_fit(image, cols as u32 * 2, rows as u32 * 4);
+    let luma = |x: u32, y: u32| -> Option<f64> {
+        let [r, g, b, a] = image.get_pixel_checked(x, y)?.0;
+        (a >= MIN_ALPHA).then_some(0.299 * r as f64 + 0.587 * g as f64 + 0.114 * b as f64)
+    };
+    let values: Vec<f64> = image
+        .enumerate_pixels()
+        .filter_map(|(x, y, _)| luma(x, y))
+        .collect();
+    let (min, max) = values
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    let threshold = if max - min < 1.0 {
+        // Flat image: light shows as dots, dark as blank
+        127.5
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    };
+
+    // Dot bits of a braille cell, indexed by (column, row) within the cell
+    const DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+    (0..image.height().div_ceil(4))
+        .map(|row| {
+            let text: String = (0..image.width().div_ceil(2))
+                .map(|col| {
+                    let mut bits = 0;
+                    for (dx, column_dots) in DOTS.iter().enumerate() {
+                        for (dy, dot) in column_dots.iter().enumerate() {
+                            let (x, y) = (col * 2 + dx as u32, row * 4 + dy as u32);
+                            if luma(x, y).is_some_and(|l| l > threshold) {
+                                bits |= dot;
+                            }
+                        }
+                    }
+                    char::from_u32(0x2800 + bits).unwrap_or(' ')
+                })
+                .collect();
+            Line::from(text)
+        })
+        .collect()
+}
+
+/// Kitty graphics command displaying `image` scaled into `cols` x `rows` cells
+/// at the cursor position
+pub fn kitty_sequence(image: &RgbaImage, cols: u16, rows: u16) -> image::ImageResult<String> {
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    let mut sequence = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,c={cols},r={rows},m={more};{chunk}\x1b\\"
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    Ok(sequence)
+}
+
+/// Kitty graphics command removing all images from the screen
+pub const KITTY_CLEAR: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// Sixel sequence drawing `image` at the cursor position, with colors
+/// quantized to a 6x6x6 cube
+pub fn sixel_sequence(image: &RgbaImage) -> String {
+    let level = |v: u8| (v as u32 * 5 + 127) / 255;
+    let color_index = |x: u32, y: u32| -> Option<usize> {
+        let [r, g, b, a] = image.get_pixel(x, y).0;
+        (a >= MIN_ALPHA).then(|| (level(r) * 36 + level(g) * 6 + level(b)) as usize)
+    };
+
+    let (width, height) = image.dimensions();
+    let mut sequence = format!("\x1bPq\"1;1;{width};{height}");
+    for index in 0..216u32 {
+        let percent = |l: u32| l * 100 / 5;
+        sequence.push_str(&format!(
+            "#{index};2;{};{};{}",
+            percent(index / 36),
+            percent(index / 6 % 6),
+            percent(index % 6)
+        ));
+    }
+
+    // Each band covers six pixel rows; colors are drawn over each other
+    for band in (0..height).step_by(6) {
+        let mut bands: Vec<Option<Vec<u8>>> = vec![None; 216];
+        for x in 0..width {
+            for dy in 0..6.min(height - band) {
+                if let Some(index) = color_index(x, band + dy) {
+                    let row = bands[index].get_or_insert_with(|| vec![0; width as usize]);
+                    row[x as usize] |= 1 << dy;
+                }
+            }
+        }
+        for (index, row) in bands.iter().enumerate() {
+            let Some(row) = row else {
+                continue;
+            };
+            sequence.push_str(&format!("#{index}"));
+            push_sixel_runs(&mut sequence, row);
+            sequence.push('$');
+        }
+        sequence.push('-');
+    }
+    sequence.push_str("\x1b\\");
+    sequence
+}
+
+/// Append a row of sixel bit patterns, run-length encoded
+fn push_sixel_runs(sequence: &mut String, row: &[u8]) {
+    let mut i = 0;
+    while i < row.len() {
+        let run = row[i..].iter().take_while(|&&bits| bits == row[i]).count();
+        let symbol = char::from(63 + row[i]);
+        if run > 3 {
+            sequence.push_str(&format!("!{run}{symbol}"));
+        } else {
+            sequence.extend(std::iter::repeat_n(symbol, run));
+        }
+        i += run;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_detect_protocol() {
+        let detect = |vars: &[(&str, &str)]| {
+            detect_protocol(|name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            })
+        };
+        assert_eq!(detect(&[("TERM", "xterm-kitty")]), ImageProtocol::Kitty);
+        assert_eq!(detect(&[("TERM_PROGRAM", "WezTerm")]), ImageProtocol::Kitty);
+        assert_eq!(detect(&[("TERM", "foot")]), ImageProtocol::Sixel);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ImageProtocol::Halfblocks
+        );
+        assert_eq!(detect(&[("TERM", "screen")]), ImageProtocol::Braille);
+    }
+
+    #[test]
+    fn test_fit_keeps_aspect_ratio() {
+        assert_eq!(fit(200, 100, 50, 50), (50, 25));
+        assert_eq!(fit(100, 400, 50, 50), (12, 50));
+        assert_eq!(fit(0, 10, 50, 50), (0, 0));
+    }
+
+    #[test]
+    fn test_halfblock_and_braille_sizes() {
+        // Left half white, right half black
+        let image = RgbaImage::from_fn(8, 8, |x, _| {
+            if x < 4 {
+                Rgba([255, 255, 255, 255])
+            } else {
+                Rgba([0, 0, 0, 255])
+            }
+        });
+
+        let lines = halfblock_lines(&image, 4, 10);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].spans.len(), 4);
+
+        let lines = braille_lines(&image, 2, 10);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].to_string(), "⣿⠀");
+    }
+
+    #[test]
+    fn test_sixel_sequence() {
+        let image = RgbaImage::from_pixel(5, 6, Rgba([255, 0, 0, 255]));
+        let sequence = sixel_sequence(&image);
+        assert!(sequence.starts_with("\x1bPq\"1;1;5;6"));
+        // Red is color 180 of the cube; all six rows set in a run of five
+        assert!(sequence.contains("#180!5~$-"));
+        assert!(sequence.ends_with("\x1b\\"));
+    }
+}
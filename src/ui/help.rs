@@ -27,7 +27,7 @@ impl HelpOverlay {
                 "Navigation",
                 vec![
                     ("↑/↓", "Move up/down in list"),
-                    ("←/→", "Scroll config left/right"),
+                    ("←/→", "Scroll config or log left/right"),
                     ("Esc", "Go back / clear search"),
                     ("Tab", "Cycle focus between panels"),
                     ("Shift+Tab", "Cycle focus backwards"),
@@ -40,6 +40,29 @@ impl HelpOverlay {
                     ("Space", "Toggle metric for overlay"),
                     ("Backspace", "Clear metric overlay"),
                     ("←/→", "Shift metric window"),
+                    ("y", "Toggle system metrics panel"),
+                    ("l", "Toggle run log panel"),
+                    ("R", "Resumed runs: latest/segments/all"),
+                    ("x", "X-axis: step/wall time/elapsed time"),
+                    ("X", "Use focused metric as x-axis"),
+                    ("L", "Y-scale of focused metric: linear/log/symlog"),
+                    ("m", "Smoothing: off/EMA/median/gaussian"),
+                    ("+/-", "Smoothing strength up/down"),
+                    ("[/]", "Previous/next step of histogram or table"),
+                    ("PgUp/PgDn", "Scroll table rows"),
+                    ("o/O", "Sort table by next column/reverse order"),
+                ],
+            ),
+            (
+                "Chart",
+                vec![
+                    ("z", "Focus chart for zoom and cursor"),
+                    ("↑/↓", "Zoom in/out on steps"),
+                    ("←/→", "Pan left/right"),
+                    ("a", "Lock y-range / fit visible points"),
+                    ("0", "Reset view"),
+                    ("c", "Show/hide step cursor with values"),
+                    (",/.", "Cursor to previous/next logged step"),
                 ],
             ),
             (
@@ -50,13 +73,22 @@ impl HelpOverlay {
                 ],
             ),
             (
-                "Config Search",
+                "Config/Log Search",
                 vec![
-                    ("/", "Search config"),
+                    ("/", "Search config or log"),
                     ("n / N", "Next/previous match"),
                     ("c", "Clear search"),
                 ],
             ),
+            (
+                "Mouse",
+                vec![
+                    ("Click", "Focus panel, select project/run/metric slot"),
+                    ("Wheel", "Scroll lists, config and log"),
+                    ("Drag on chart", "Zoom into a step range"),
+                    ("Hover on chart", "Move the step cursor"),
+                ],
+            ),
             (
                 "General",
                 vec![
@@ -0,0 +1,307 @@
+//! Histogram view: a heat map of a histogram series over steps, with a step
+//! cursor whose bins are shown as bars below it.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+    Frame,
+};
+
+use super::axis;
+use crate::cli::ValueFormat;
+use crate::data::Histogram;
+
+/// Shades for bin density, from empty to the fullest bin of a row
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Width of the step label column left of the heat map
+const GUTTER_WIDTH: u16 = 9;
+
+/// Heat map color
+const HEAT_COLOR: Color = Color::Indexed(36); // Teal
+
+/// Color of the cursor row and its bars
+const CURSOR_COLOR: Color = Color::Yellow;
+
+/// Chart-area view of one histogram series
+pub struct HistogramView<'a> {
+    histogram: &'a Histogram,
+    /// Index of the step under the cursor
+    cursor: usize,
+}
+
+impl<'a> HistogramView<'a> {
+    pub fn new(histogram: &'a Histogram, cursor: usize) -> Self {
+        let cursor = cursor.min(histogram.points.len().saturating_sub(1));
+        HistogramView { histogram, cursor }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let points = &self.histogram.points;
+        let title = match points.get(self.cursor) {
+            Some(point) => format!(
+                " {} · step {} ({}/{}) ",
+                self.histogram.name,
+                point.step,
+                self.cursor + 1,
+                points.len()
+            ),
+            None => format!(" {} ", self.histogram.name),
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some((lo, hi)) = value_range(self.histogram) else {
+            frame.render_widget(
+                Paragraph::new("No data").alignment(Alignment::Center),
+                inner,
+            );
+            return;
+        };
+
+        // Heat map on top, the cursor step's bins and the value range below
+        let bars_height = (inner.height / 3).max(3);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(bars_height),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        let columns = |area: Rect| {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(GUTTER_WIDTH), Constraint::Min(1)])
+                .split(area)
+        };
+        let (heat_area, bars_area, range_area) = (
+            columns(rows[0])[1],
+            columns(rows[1])[1],
+            columns(rows[2])[1],
+        );
+        let width = heat_area.width as usize;
+
+        // Heat map: one row per shown step, oldest on top
+        let lines: Vec<Line> = sample_rows(points.len(), self.cursor, rows[0].height as usize)
+            .into_iter()
+            .map(|idx| {
+                let point = &points[idx];
+                let bins = rebin(&point.edges, &point.counts, lo, hi, width);
+                let max = bins.iter().copied().fold(0.0, f64::max);
+                let cells: String = bins
+                    .iter()
+                    .map(|&count| {
+                        let level = if max > 0.0 {
+                            ((count / max) * (SHADES.len() - 1) as f64).ceil() as usize
+                        } else {
+                            0
+                        };
+                        SHADES[level.min(SHADES.len() - 1)]
+                    })
+                    .collect();
+                let (marker, color) = if idx == self.cursor {
+                    ('>', CURSOR_COLOR)
+                } else {
+                    (' ', HEAT_COLOR)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{marker}{:>7} ", point.step),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(cells, Style::default().fg(color)),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), rows[0]);
+
+        // Bars of the cursor step, scaled to its fullest bin
+        let point = &points[self.cursor];
+        let bins = rebin(&point.edges, &point.counts, lo, hi, width);
+        let max = bins.iter().copied().fold(0.0, f64::max);
+        let bars: Vec<u64> = bins
+            .iter()
+            .map(|&count| {
+                if max > 0.0 {
+                    (count / max * 1000.0).round() as u64
+                } else {
+                    0
+                }
+            })
+            .collect();
+        frame.render_widget(
+            Sparkline::default()
+                .data(&bars)
+                .max(1000)
+                .style(Style::default().fg(CURSOR_COLOR)),
+            bars_area,
+        );
+        let peak = point.counts.iter().copied().fold(0.0, f64::max);
+        let peak = format!("max {}", format_label(peak, 0.0));
+        frame.render_widget(
+            Paragraph::new(peak).style(Style::default().add_modifier(Modifier::DIM)),
+            columns(rows[1])[0],
+        );
+
+        // Value range under the bars
+        let (lo_label, hi_label) = (format_label(lo, hi), format_label(hi, lo));
+        let gap = width.saturating_sub(lo_label.len() + hi_label.len());
+        frame.render_widget(
+            Paragraph::new(format!("{lo_label}{}{hi_label}", " ".repeat(gap)))
+                .style(Style::default().fg(Color::DarkGray)),
+            range_area,
+        );
+    }
+}
+
+/// Smallest and largest bin edge over all steps
+fn value_range(histogram: &Histogram) -> Option<(f64, f64)> {
+    let lo = histogram
+        .points
+        .iter()
+        .filter_map(|p| p.edges.first())
+        .copied()
+        .reduce(f64::min)?;
+    let hi = histogram
+        .points
+        .iter()
+        .filter_map(|p| p.edges.last())
+        .copied()
+        .reduce(f64::max)?;
+    if hi > lo {
+        Some((lo, hi))
+    } else {
+        Some((lo - 0.5, lo + 0.5))
+    }
+}
+
+/// Indices of the steps to show in `height` rows: evenly spaced over all
+/// steps, always including the cursor
+fn sample_rows(len: usize, cursor: usize, height: usize) -> Vec<usize> {
+    if len <= height {
+        return (0..len).collect();
+    }
+    if height <= 1 {
+        return vec![cursor];
+    }
+    let mut rows: Vec<usize> = (0..height).map(|i| i * (len - 1) / (height - 1)).collect();
+    if !rows.contains(&cursor) {
+        // Replacing the nearest sampled step keeps the rows in order
+        if let Some(nearest) = rows.iter_mut().min_by_key(|idx| idx.abs_diff(cursor)) {
+            *nearest = cursor;
+        }
+    }
+    rows
+}
+
+/// Redistribute bin counts over `width` equal columns spanning `lo..hi`,
+/// assuming counts are spread evenly within each bin
+fn rebin(edges: &[f64], counts: &[f64], lo: f64, hi: f64, width: usize) -> Vec<f64> {
+    let mut columns = vec![0.0; width];
+    if width == 0 || hi <= lo {
+        return columns;
+    }
+    let column_width = (hi - lo) / width as f64;
+    let column_of = |x: f64| (((x - lo) / column_width) as usize).min(width - 1);
+
+    for (bin, &count) in edges.windows(2).zip(counts) {
+        let (start, end) = (bin[0].max(lo), bin[1].min(hi));
+        if end < start {
+            continue;
+        }
+        if end == start {
+            columns[column_of(start)] += count;
+            continue;
+        }
+        let density = count / (bin[1] - bin[0]);
+        for (col, total) in columns
+            .iter_mut()
+            .enumerate()
+            .take(column_of(end) + 1)
+            .skip(column_of(start))
+        {
+            let col_start = lo + col as f64 * column_width;
+            let overlap = end.min(col_start + column_width) - start.max(col_start);
+            if overlap > 0.0 {
+                *total += density * overlap;
+            }
+        }
+    }
+    columns
+}
+
+/// Label for a bin edge or count, precise enough to tell it apart from `other`
+/// (the other end of its range), formatted like the chart axes
+fn format_label(value: f64, other: f64) -> String {
+    let precision = axis::tick_precision(&[other, value]);
+    axis::format_value(value, precision, ValueFormat::Auto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::HistogramPoint;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_rebin_splits_and_merges_bins() {
+        // Two bins onto four columns: each bin spreads over two columns
+        let columns = rebin(&[0.0, 2.0, 4.0], &[4.0, 8.0], 0.0, 4.0, 4);
+        assert_eq!(columns, vec![2.0, 2.0, 4.0, 4.0]);
+
+        // Four bins onto two columns: neighbours are summed
+        let columns = rebin(
+            &[0.0, 1.0, 2.0, 3.0, 4.0],
+            &[1.0, 2.0, 3.0, 4.0],
+            0.0,
+            4.0,
+            2,
+        );
+        assert_eq!(columns, vec![3.0, 7.0]);
+
+        // Counts are kept when bins cover only part of the range
+        let columns = rebin(&[1.0, 2.0], &[6.0], 0.0, 4.0, 4);
+        assert_eq!(columns, vec![0.0, 6.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_sample_rows_keeps_cursor() {
+        assert_eq!(sample_rows(3, 1, 10), vec![0, 1, 2]);
+        let rows = sample_rows(100, 42, 5);
+        assert_eq!(rows.len(), 5);
+        assert!(rows.contains(&42));
+        assert!(rows.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_histogram_view_title() {
+        let histogram = Histogram {
+            name: "weights".to_string(),
+            points: (0..3)
+                .map(|step| HistogramPoint {
+                    step: step * 10,
+                    timestamp: None,
+                    edges: vec![0.0, 1.0, 2.0],
+                    counts: vec![1.0, step as f64],
+                })
+                .collect(),
+        };
+        let mut terminal = Terminal::new(TestBackend::new(50, 15)).unwrap();
+        terminal
+            .draw(|frame| HistogramView::new(&histogram, 1).render(frame, frame.area()))
+            .unwrap();
+        let rendered: String = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(rendered.contains("weights · step 10 (2/3)"));
+        assert!(rendered.contains(">     10"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Log panel: the text values of a run (status strings, generated samples,
+//! notes) in logging order, searchable like the config panel.
+
+use ratatui::{layout::Rect, Frame};
+
+use super::widgets::{render_search_panel, ConfigPanelState};
+use crate::cli::DisplayTimezone;
+use crate::data::RichValues;
+
+/// Lines of the log panel, one per line of each text entry. Continuation
+/// lines of multi-line text are indented under the first. Times are shown in `timezone`.
+pub fn log_lines(rich_values: &RichValues, timezone: DisplayTimezone) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (name, point) in rich_values.text_entries() {
+        let time = point
+            .timestamp
+            .map(|ts| timezone.format(ts, "%H:%M:%S"))
+            .unwrap_or_else(|| "--:--:--".to_string());
+        let prefix = format!("{:>7}  {time}  {name}: ", point.step);
+        let mut text_lines = point.text.lines();
+        lines.push(format!("{prefix}{}", text_lines.next().unwrap_or_default()));
+        let indent = " ".repeat(prefix.chars().count());
+        lines.extend(text_lines.map(|line| format!("{indent}{line}")));
+    }
+    lines
+}
+
+/// Log panel widget
+pub struct LogPanel<'a> {
+    lines: &'a [String],
+    state: &'a ConfigPanelState,
+}
+
+impl<'a> LogPanel<'a> {
+    pub fn new(lines: &'a [String], state: &'a ConfigPanelState) -> Self {
+        LogPanel { lines, state }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, focused: bool) {
+        render_search_panel(frame, area, focused, "Log", self.lines, self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{TextPoint, TextSeries};
+
+    #[test]
+    fn test_log_lines_ordered_by_step() {
+        let series = |name: &str, points: &[(i64, &str)]| TextSeries {
+            name: name.to_string(),
+            points: points
+                .iter()
+                .map(|&(step, text)| TextPoint {
+                    step,
+                    timestamp: None,
+                    text: text.to_string(),
+                })
+                .collect(),
+        };
+        let rich_values = RichValues {
+            texts: vec![
+                series("sample", &[(5, "Once upon\na time")]),
+                series("status", &[(0, "start"), (10, "done")]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            log_lines(&rich_values, DisplayTimezone::Local),
+            vec![
+                "      0  --:--:--  status: start",
+                "      5  --:--:--  sample: Once upon",
+                "                           a time",
+                "     10  --:--:--  status: done",
+            ]
+        );
+    }
+}
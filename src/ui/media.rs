@@ -0,0 +1,359 @@
+//! Media view: the files of a media series listed per step, with a preview of
+//! the selected image drawn in the terminal.
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use image::RgbaImage;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::graphics;
+use crate::cli::ImageProtocol;
+use crate::data::Media;
+
+/// Widest the entry list gets, in characters
+const MAX_LIST_WIDTH: u16 = 32;
+
+/// An image to draw with terminal graphics after the frame is flushed
+#[derive(Debug, Clone, PartialEq)]
+struct GraphicsRequest {
+    path: PathBuf,
+    area: Rect,
+}
+
+/// Decoded image cache and terminal graphics state for the media view
+pub struct MediaPreview {
+    protocol: ImageProtocol,
+    /// Last decoded file; decoding on every frame would be too slow
+    decoded: RefCell<Option<(PathBuf, Result<RgbaImage, String>)>>,
+    /// Image requested by the frame being rendered
+    pending: RefCell<Option<GraphicsRequest>>,
+    /// Image currently on screen
+    drawn: Option<GraphicsRequest>,
+}
+
+impl MediaPreview {
+    /// Create a preview drawing images with `protocol` (`Auto` is resolved from the environment)
+    pub fn new(protocol: ImageProtocol) -> Self {
+        let protocol = match protocol {
+            ImageProtocol::Auto => graphics::detect_protocol(|name| std::env::var(name).ok()),
+            protocol => protocol,
+        };
+        MediaPreview {
+            protocol,
+            decoded: RefCell::new(None),
+            pending: RefCell::new(None),
+            drawn: None,
+        }
+    }
+
+    /// Whether images are drawn with escape sequences rather than text cells
+    fn uses_terminal_graphics(&self) -> bool {
+        matches!(self.protocol, ImageProtocol::Kitty | ImageProtocol::Sixel)
+    }
+
+    /// Run `f` on the decoded image at `path`
+    fn with_image<T>(&self, path: &Path, f: impl FnOnce(&RgbaImage) -> T) -> Result<T, String> {
+        let mut decoded = self.decoded.borrow_mut();
+        if decoded.as_ref().is_none_or(|(cached, _)| cached != path) {
+            let image = image::open(path)
+                .map(|image| image.to_rgba8())
+                .map_err(|e| e.to_string());
+            *decoded = Some((path.to_path_buf(), image));
+        }
+        match decoded.as_ref() {
+            Some((_, Ok(image))) => Ok(f(image)),
+            Some((_, Err(e))) => Err(e.clone()),
+            None => unreachable!("decoded above"),
+        }
+    }
+
+    /// Drop any image requested by the frame being rendered (e.g. under a popup)
+    pub fn cancel_graphics(&self) {
+        self.pending.borrow_mut().take();
+    }
+
+    /// Write the image requested by the last frame using terminal graphics.
+    ///
+    /// Returns true when a sixel image has to be erased first: the caller
+    /// should clear the terminal, render again and flush again.
+    pub fn flush(&mut self, out: &mut impl Write) -> io::Result<bool> {
+        let pending = self.pending.borrow_mut().take();
+        if pending == self.drawn || !self.uses_terminal_graphics() {
+            return Ok(false);
+        }
+
+        if self.protocol == ImageProtocol::Sixel && self.drawn.is_some() {
+            // Sixel pixels stay until the cells under them are redrawn
+            self.drawn = None;
+            return Ok(true);
+        }
+        if self.protocol == ImageProtocol::Kitty {
+            out.write_all(graphics::KITTY_CLEAR.as_bytes())?;
+        }
+
+        if let Some(request) = &pending {
+            let (cell_width, cell_height) = graphics::cell_size();
+            let max_width = request.area.width as u32 * cell_width;
+            let max_height = request.area.height as u32 * cell_height;
+            let sequence = self.with_image(&request.path, |image| {
+                let image = graphics::resize_to_fit(image, max_width, max_height);
+                match self.protocol {
+                    ImageProtocol::Kitty => {
+                        let cols = image.width().div_ceil(cell_width) as u16;
+                        let rows = image.height().div_ceil(cell_height) as u16;
+                        graphics::kitty_sequence(&image, cols, rows).unwrap_or_default()
+                    }
+                    _ => graphics::sixel_sequence(&image),
+                }
+            });
+            if let Ok(sequence) = sequence {
+                crossterm::queue!(
+                    out,
+                    crossterm::cursor::MoveTo(request.area.x, request.area.y),
+                    crossterm::style::Print(sequence)
+                )?;
+            }
+        }
+        out.flush()?;
+        self.drawn = pending;
+        Ok(false)
+    }
+}
+
+/// Chart-area view of one media series
+pub struct MediaView<'a> {
+    media: &'a Media,
+    /// Index of the selected entry
+    cursor: usize,
+    preview: &'a MediaPreview,
+}
+
+impl<'a> MediaView<'a> {
+    pub fn new(media: &'a Media, cursor: usize, preview: &'a MediaPreview) -> Self {
+        let cursor = cursor.min(media.points.len().saturating_sub(1));
+        MediaView {
+            media,
+            cursor,
+            preview,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let points = &self.media.points;
+        let title = match points.get(self.cursor) {
+            Some(point) => format!(
+                " {} · step {} ({}/{}) ",
+                self.media.name,
+                point.step,
+                self.cursor + 1,
+                points.len()
+            ),
+            None => format!(" {} ", self.media.name),
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let Some(point) = points.get(self.cursor) else {
+            frame.render_widget(
+                Paragraph::new("No data").alignment(Alignment::Center),
+                inner,
+            );
+            return;
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length((inner.width / 3).min(MAX_LIST_WIDTH)),
+                Constraint::Min(1),
+            ])
+            .split(inner);
+
+        // Entry list, scrolled to keep the cursor in view
+        let height = chunks[0].height as usize;
+        let first = self
+            .cursor
+            .saturating_sub(height / 2)
+            .min(points.len().saturating_sub(height));
+        let lines: Vec<Line> = points
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(height)
+            .map(|(idx, point)| {
+                let label = point
+                    .caption
+                    .clone()
+                    .unwrap_or_else(|| file_name(&point.path));
+                let line = Line::from(format!("{:>7} {label}", point.step));
+                if idx == self.cursor {
+                    line.style(Style::default().add_modifier(Modifier::REVERSED))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::RIGHT)),
+            chunks[0],
+        );
+
+        // Preview under a line naming the file
+        let preview_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(chunks[1]);
+        let heading = match &point.caption {
+            Some(caption) => format!(" {caption} ({})", file_name(&point.path)),
+            None => format!(" {}", file_name(&point.path)),
+        };
+        frame.render_widget(
+            Paragraph::new(heading).style(Style::default().fg(Color::DarkGray)),
+            preview_chunks[0],
+        );
+        self.render_preview(frame, preview_chunks[1], point.kind.as_str(), &point.path);
+    }
+
+    fn render_preview(&self, frame: &mut Frame, area: Rect, kind: &str, path: &Path) {
+        let message = |text: String| {
+            Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+        };
+        if kind != "image" {
+            frame.render_widget(
+                message(format!("No preview for {kind} files\n{}", path.display())),
+                area,
+            );
+            return;
+        }
+
+        let preview = self.preview;
+        let lines = preview.with_image(path, |image| match preview.protocol {
+            ImageProtocol::Halfblocks => graphics::halfblock_lines(image, area.width, area.height),
+            ImageProtocol::Braille => graphics::braille_lines(image, area.width, area.height),
+            // Drawn after the frame, over these (blank) cells
+            _ => Vec::new(),
+        });
+        match lines {
+            Ok(_) if preview.uses_terminal_graphics() => {
+                *preview.pending.borrow_mut() = Some(GraphicsRequest {
+                    path: path.to_path_buf(),
+                    area,
+                });
+            }
+            Ok(lines) => {
+                frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area)
+            }
+            Err(e) => frame.render_widget(
+                message(format!("Cannot open {}: {e}", path.display())),
+                area,
+            ),
+        }
+    }
+}
+
+/// File name of a media path, for listing
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MediaPoint;
+    use image::Rgba;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render_to_string(view: &MediaView, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| view.render(frame, frame.area()))
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_media_view_lists_entries_and_previews() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.png");
+        RgbaImage::from_pixel(4, 4, Rgba([255, 255, 255, 255]))
+            .save(&path)
+            .unwrap();
+        let media = Media {
+            name: "samples".to_string(),
+            points: vec![
+                MediaPoint {
+                    step: 5,
+                    timestamp: None,
+                    kind: "image".to_string(),
+                    path: path.clone(),
+                    caption: Some("a cat".to_string()),
+                },
+                MediaPoint {
+                    step: 10,
+                    timestamp: None,
+                    kind: "image".to_string(),
+                    path: dir.path().join("missing.png"),
+                    caption: None,
+                },
+            ],
+        };
+
+        let preview = MediaPreview::new(ImageProtocol::Braille);
+        let rendered = render_to_string(&MediaView::new(&media, 0, &preview), 90, 12);
+        assert!(rendered.contains("samples · step 5 (1/2)"));
+        assert!(rendered.contains("a cat (sample.png)"));
+        assert!(rendered.contains("missing.png"));
+        assert!(rendered.contains('⣿'));
+
+        let rendered = render_to_string(&MediaView::new(&media, 1, &preview), 90, 12);
+        assert!(rendered.contains("Cannot open"));
+    }
+
+    #[test]
+    fn test_flush_writes_graphics_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sample.png");
+        RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]))
+            .save(&path)
+            .unwrap();
+        let request = GraphicsRequest {
+            path,
+            area: Rect::new(2, 3, 10, 5),
+        };
+
+        let mut preview = MediaPreview::new(ImageProtocol::Sixel);
+        let mut out = Vec::new();
+        *preview.pending.borrow_mut() = Some(request.clone());
+        assert!(!preview.flush(&mut out).unwrap());
+        assert!(String::from_utf8_lossy(&out).contains("\x1bPq"));
+
+        // Unchanged frames write nothing
+        out.clear();
+        *preview.pending.borrow_mut() = Some(request);
+        assert!(!preview.flush(&mut out).unwrap());
+        assert!(out.is_empty());
+
+        // Removing a sixel image needs a full redraw
+        assert!(preview.flush(&mut out).unwrap());
+        assert!(!preview.flush(&mut out).unwrap());
+    }
+}
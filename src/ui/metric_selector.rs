@@ -5,6 +5,7 @@
 //! shifted while keeping the slot selection fixed.
 
 use std::collections::HashSet;
+use std::ops::Range;
 
 use ratatui::{layout::Rect, widgets::Paragraph, Frame};
 
@@ -159,10 +160,26 @@ impl<'a> MetricSelector<'a> {
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let (text, _) = self.text();
+        let paragraph = Paragraph::new(text);
+        frame.render_widget(paragraph, area);
+    }
+
+    /// Slot under `column`, counted from the left edge of the selector
+    pub fn slot_at(&self, column: u16) -> Option<usize> {
+        let (_, slots) = self.text();
+        slots
+            .iter()
+            .position(|range| range.contains(&(column as usize)))
+    }
+
+    /// Text of the selector bar, with the column range of each slot's label
+    fn text(&self) -> (String, Vec<Range<usize>>) {
         let num_metrics = self.metrics.len();
         let num_visible = self.state.num_visible_slots(num_metrics);
 
         let mut text = String::new();
+        let mut slots = Vec::with_capacity(num_visible);
 
         // Left indicator: show "<" if there are more metrics than slots (circular)
         if self.state.has_more_left(num_metrics) {
@@ -183,13 +200,11 @@ impl<'a> MetricSelector<'a> {
             } else {
                 ""
             };
-            text.push_str(&format!(
-                "[{}] {}{}{}  ",
-                slot + 1,
-                name,
-                focus_marker,
-                select_marker
-            ));
+            let label = format!("[{}] {}{}{}", slot + 1, name, focus_marker, select_marker);
+            let start = text.chars().count();
+            slots.push(start..start + label.chars().count());
+            text.push_str(&label);
+            text.push_str("  ");
         }
 
         // Right indicator: show ">" if there are more metrics than slots (circular)
@@ -197,8 +212,7 @@ impl<'a> MetricSelector<'a> {
             text.push_str(" >");
         }
 
-        let paragraph = Paragraph::new(text);
-        frame.render_widget(paragraph, area);
+        (text, slots)
     }
 }
 
@@ -469,4 +483,17 @@ mod tests {
         // Now slot 4 shows metric (8 + 4) % 12 = 0 (metric "1")
         assert_eq!(state.selected_metric(num_metrics), 0);
     }
+
+    #[test]
+    fn test_slot_at_column() {
+        let metrics = vec!["loss".to_string(), "acc".to_string()];
+        let state = MetricSlotState::new();
+        let selector = MetricSelector::new(&metrics, &state);
+        // "[1] loss*  [2] acc  "
+        assert_eq!(selector.slot_at(0), Some(0));
+        assert_eq!(selector.slot_at(8), Some(0));
+        assert_eq!(selector.slot_at(10), None);
+        assert_eq!(selector.slot_at(11), Some(1));
+        assert_eq!(selector.slot_at(30), None);
+    }
 }
@@ -1,8 +1,16 @@
 //! Terminal User Interface components for trackio-tui.
 
+pub mod axis;
 pub mod chart;
+mod graphics;
 mod help;
+pub mod histogram;
+pub mod log;
+pub mod media;
 pub mod metric_selector;
+pub mod smoothing;
+pub mod system;
+pub mod table;
 pub mod widgets;
 
 pub use help::HelpOverlay;
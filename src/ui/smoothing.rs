@@ -0,0 +1,232 @@
+//! Curve smoothing for noisy metrics: TensorBoard-style exponential moving
+//! average, rolling median and Gaussian window.
+
+/// Change of the strength per key press
+const STRENGTH_STEP: f64 = 0.05;
+
+/// Highest strength; an EMA weight of 1 would never move
+const MAX_STRENGTH: f64 = 0.99;
+
+/// Strength used until changed (TensorBoard's default)
+const DEFAULT_STRENGTH: f64 = 0.6;
+
+/// Points on each side of the rolling median and Gaussian windows at strength 1
+const MAX_HALF_WINDOW: f64 = 50.0;
+
+/// How lines are smoothed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SmoothingMode {
+    #[default]
+    Off,
+    /// Debiased exponential moving average, weighted by the strength
+    Ema,
+    /// Median of a centered window of points
+    Median,
+    /// Gaussian-weighted mean of a centered window of points
+    Gaussian,
+}
+
+impl SmoothingMode {
+    fn next(self) -> Self {
+        match self {
+            SmoothingMode::Off => SmoothingMode::Ema,
+            SmoothingMode::Ema => SmoothingMode::Median,
+            SmoothingMode::Median => SmoothingMode::Gaussian,
+            SmoothingMode::Gaussian => SmoothingMode::Off,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SmoothingMode::Off => "off",
+            SmoothingMode::Ema => "EMA",
+            SmoothingMode::Median => "median",
+            SmoothingMode::Gaussian => "gaussian",
+        }
+    }
+}
+
+/// Smoothing mode and strength (0 to 0.99) applied to chart lines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Smoothing {
+    pub mode: SmoothingMode,
+    pub strength: f64,
+}
+
+impl Default for Smoothing {
+    fn default() -> Self {
+        Smoothing {
+            mode: SmoothingMode::default(),
+            strength: DEFAULT_STRENGTH,
+        }
+    }
+}
+
+impl Smoothing {
+    /// Switch to the next mode, keeping the strength
+    pub fn next_mode(&mut self) {
+        self.mode = self.mode.next();
+    }
+
+    /// Raise (positive `steps`) or lower the strength by whole steps
+    pub fn adjust(&mut self, steps: i32) {
+        let strength = self.strength + steps as f64 * STRENGTH_STEP;
+        // Round so repeated steps land on the same values
+        self.strength = ((strength * 100.0).round() / 100.0).clamp(0.0, MAX_STRENGTH);
+    }
+
+    /// Whether lines are changed at all
+    pub fn is_active(&self) -> bool {
+        self.mode != SmoothingMode::Off && self.strength > 0.0
+    }
+
+    /// Mode and strength for the chart title, e.g. "EMA 0.60"
+    pub fn label(&self) -> Option<String> {
+        self.is_active()
+            .then(|| format!("{} {:.2}", self.mode.label(), self.strength))
+    }
+
+    /// Points on each side of the median and Gaussian windows
+    fn half_window(&self) -> usize {
+        (self.strength * MAX_HALF_WINDOW).round() as usize
+    }
+
+    /// Smoothed copy of a line's finite points, in the same order
+    pub fn apply(&self, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let points: Vec<(f64, f64)> = points
+            .iter()
+            .copied()
+            .filter(|(_, y)| y.is_finite())
+            .collect();
+        if !self.is_active() {
+            return points;
+        }
+        let ys: Vec<f64> = points.iter().map(|&(_, y)| y).collect();
+        let smoothed = match self.mode {
+            SmoothingMode::Off => ys,
+            SmoothingMode::Ema => ema(&ys, self.strength),
+            SmoothingMode::Median => rolling_median(&ys, self.half_window()),
+            SmoothingMode::Gaussian => gaussian(&ys, self.half_window()),
+        };
+        points
+            .iter()
+            .zip(smoothed)
+            .map(|(&(x, _), y)| (x, y))
+            .collect()
+    }
+}
+
+/// Exponential moving average, debiased so early values aren't pulled towards 0
+fn ema(ys: &[f64], weight: f64) -> Vec<f64> {
+    let mut last = 0.0;
+    let mut total_weight = 0.0;
+    ys.iter()
+        .map(|&y| {
+            last = last * weight + (1.0 - weight) * y;
+            total_weight = total_weight * weight + (1.0 - weight);
+            last / total_weight
+        })
+        .collect()
+}
+
+/// Median of the values up to `half` points on each side (fewer at the ends)
+fn rolling_median(ys: &[f64], half: usize) -> Vec<f64> {
+    let mut window = Vec::with_capacity(2 * half + 1);
+    (0..ys.len())
+        .map(|i| {
+            window.clear();
+            window.extend_from_slice(&ys[i.saturating_sub(half)..(i + half + 1).min(ys.len())]);
+            let len = window.len();
+            let (below, &mut upper, _) = window.select_nth_unstable_by(len / 2, f64::total_cmp);
+            if len % 2 == 1 {
+                upper
+            } else {
+                let lower = below.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                (lower + upper) / 2.0
+            }
+        })
+        .collect()
+}
+
+/// Gaussian-weighted mean of the values up to `half` points on each side,
+/// with the window spanning three standard deviations
+fn gaussian(ys: &[f64], half: usize) -> Vec<f64> {
+    if half == 0 {
+        return ys.to_vec();
+    }
+    let sigma = half as f64 / 3.0;
+    let kernel: Vec<f64> = (0..=half)
+        .map(|d| (-0.5 * (d as f64 / sigma).powi(2)).exp())
+        .collect();
+    (0..ys.len())
+        .map(|i| {
+            let (mut sum, mut total_weight) = (0.0, 0.0);
+            for j in i.saturating_sub(half)..(i + half + 1).min(ys.len()) {
+                let weight = kernel[i.abs_diff(j)];
+                sum += weight * ys[j];
+                total_weight += weight;
+            }
+            sum / total_weight
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smooth(mode: SmoothingMode, strength: f64, ys: &[f64]) -> Vec<f64> {
+        let points: Vec<(f64, f64)> = ys.iter().enumerate().map(|(x, &y)| (x as f64, y)).collect();
+        Smoothing { mode, strength }
+            .apply(&points)
+            .into_iter()
+            .map(|(_, y)| y)
+            .collect()
+    }
+
+    #[test]
+    fn test_ema_is_debiased() {
+        // A constant line stays constant from the first point
+        let smoothed = smooth(SmoothingMode::Ema, 0.9, &[2.0, 2.0, 2.0]);
+        assert!(smoothed.iter().all(|y| (y - 2.0).abs() < 1e-12));
+
+        let smoothed = smooth(SmoothingMode::Ema, 0.5, &[0.0, 3.0]);
+        assert_eq!(smoothed, [0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_window_modes() {
+        // Strength 0.02 is one point on each side
+        let ys = [1.0, 100.0, 3.0, 4.0];
+        assert_eq!(
+            smooth(SmoothingMode::Median, 0.02, &ys),
+            [50.5, 3.0, 4.0, 3.5]
+        );
+
+        let smoothed = smooth(SmoothingMode::Gaussian, 0.02, &[0.0, 3.0, 0.0]);
+        assert!(smoothed[1] < 3.0 && smoothed[1] > 1.0);
+        assert_eq!(smoothed[0], smoothed[2]);
+    }
+
+    #[test]
+    fn test_off_and_non_finite() {
+        let ys = [1.0, f64::NAN, 3.0];
+        assert_eq!(smooth(SmoothingMode::Off, 0.6, &ys), [1.0, 3.0]);
+        assert_eq!(smooth(SmoothingMode::Ema, 0.0, &ys), [1.0, 3.0]);
+        assert_eq!(smooth(SmoothingMode::Median, 0.6, &ys), [2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_adjust_strength() {
+        let mut smoothing = Smoothing::default();
+        assert_eq!(smoothing.label(), None);
+        smoothing.next_mode();
+        smoothing.adjust(1);
+        assert_eq!(smoothing.label().as_deref(), Some("EMA 0.65"));
+        smoothing.adjust(20);
+        assert_eq!(smoothing.strength, MAX_STRENGTH);
+        smoothing.adjust(-40);
+        assert_eq!(smoothing.strength, 0.0);
+        assert!(!smoothing.is_active());
+    }
+}
@@ -0,0 +1,167 @@
+//! System metrics panel: one small chart per hardware metric (CPU, GPU, memory).
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    symbols::Marker,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+use super::axis;
+use super::chart::calculate_bounds;
+use crate::cli::ValueFormat;
+use crate::data::{Metric, SYSTEM_METRIC_PREFIX};
+
+/// Minimum height of one small chart, including its border
+const MIN_CHART_HEIGHT: u16 = 5;
+
+/// Line color of the small charts
+const CHART_COLOR: Color = Color::Indexed(36); // Teal
+
+/// Panel of small charts for a run's system metrics
+pub struct SystemPanel<'a> {
+    metrics: &'a [&'a Metric],
+    loading: bool,
+}
+
+impl<'a> SystemPanel<'a> {
+    pub fn new(metrics: &'a [&'a Metric], loading: bool) -> Self {
+        SystemPanel { metrics, loading }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        // Show as many charts as fit; the title says how many are hidden
+        let fit = (area.height.saturating_sub(2) / MIN_CHART_HEIGHT).max(1) as usize;
+        let shown = self.metrics.len().min(fit);
+        let title = if shown < self.metrics.len() {
+            format!(" System (+{} more) ", self.metrics.len() - shown)
+        } else {
+            " System ".to_string()
+        };
+
+        let block = Block::default().title(title).borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if shown == 0 {
+            let text = if self.loading {
+                "Loading…"
+            } else {
+                "No system metrics"
+            };
+            frame.render_widget(Paragraph::new(text).alignment(Alignment::Center), inner);
+            return;
+        }
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, shown as u32); shown])
+            .split(inner);
+        for (metric, row) in self.metrics.iter().zip(rows.iter()) {
+            render_small_chart(frame, *row, metric);
+        }
+    }
+}
+
+/// Render one metric as a compact line chart titled with its latest value
+fn render_small_chart(frame: &mut Frame, area: Rect, metric: &Metric) {
+    let points: Vec<(f64, f64)> = metric
+        .points()
+        .iter()
+        .map(|p| (p.step as f64, p.value))
+        .collect();
+    let (x_bounds, y_bounds) = calculate_bounds(std::slice::from_ref(&points));
+
+    let name = metric
+        .name
+        .strip_prefix(SYSTEM_METRIC_PREFIX)
+        .unwrap_or(&metric.name);
+    // Latest value to a tenth, or a thousandth of its magnitude for large values
+    let title = match points.last() {
+        Some(&(_, value)) => {
+            let precision = (value.abs() * 1e-3).max(0.1);
+            let value = axis::format_value(value, precision, ValueFormat::Auto);
+            format!(" {name}: {value} ")
+        }
+        None => format!(" {name} "),
+    };
+    let y_precision = axis::tick_precision(&[y_bounds.0, y_bounds.1]);
+
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(CHART_COLOR))
+        .data(&points);
+
+    let chart = Chart::new(vec![dataset])
+        .block(Block::default().title(title).borders(Borders::TOP))
+        .x_axis(Axis::default().bounds([x_bounds.0, x_bounds.1]))
+        .y_axis(
+            Axis::default()
+                .bounds([y_bounds.0, y_bounds.1])
+                .labels(vec![
+                    axis::format_value(y_bounds.0, y_precision, ValueFormat::Auto),
+                    axis::format_value(y_bounds.1, y_precision, ValueFormat::Auto),
+                ]),
+        )
+        .legend_position(None);
+    frame.render_widget(chart, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MetricPoint;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn system_metric(name: &str, values: &[f64]) -> Metric {
+        Metric::with_points(
+            name.to_string(),
+            values
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| MetricPoint {
+                    step: i as i64,
+                    value: v,
+                    timestamp: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn render_to_string(panel: &SystemPanel, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| panel.render(frame, frame.area()))
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_system_panel_titles_and_overflow() {
+        let cpu = system_metric("system/cpu", &[10.0, 42.0]);
+        let gpu = system_metric("system/gpu0_util", &[0.0, 99.0]);
+        let mem = system_metric("system/memory", &[1.0]);
+        let metrics = [&cpu, &gpu, &mem];
+
+        // Room for two charts only
+        let rendered = render_to_string(&SystemPanel::new(&metrics, false), 40, 12);
+        assert!(rendered.contains("System (+1 more)"));
+        assert!(rendered.contains("cpu: 42.0"));
+        assert!(rendered.contains("gpu0_util: 99.0"));
+        assert!(!rendered.contains("memory"));
+    }
+
+    #[test]
+    fn test_system_panel_empty() {
+        let rendered = render_to_string(&SystemPanel::new(&[], false), 30, 8);
+        assert!(rendered.contains("No system metrics"));
+    }
+}
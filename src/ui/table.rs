@@ -0,0 +1,241 @@
+//! Table view: one logged version of a table series, scrollable and sortable
+//! by column.
+
+use std::cmp::Ordering;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table as TableWidget},
+    Frame,
+};
+
+use crate::data::{ConfigValue, Table};
+
+/// Widest a column is sized for, in characters
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Scroll and sort state of the table view
+#[derive(Debug, Clone, Default)]
+pub struct TableViewState {
+    /// Index of the first visible row
+    pub scroll: usize,
+    /// Column rows are sorted by; `None` keeps the logged order
+    pub sort_column: Option<usize>,
+    pub descending: bool,
+}
+
+impl TableViewState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset scrolling and sorting (e.g. when switching run)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Sort by the next column; after the last column, return to the logged order
+    pub fn cycle_sort(&mut self, num_columns: usize) {
+        self.sort_column = match self.sort_column {
+            None if num_columns > 0 => Some(0),
+            Some(col) if col + 1 < num_columns => Some(col + 1),
+            _ => None,
+        };
+    }
+
+    /// Switch between ascending and descending order
+    pub fn toggle_order(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    /// Scroll by `delta` rows, keeping at least one row visible
+    pub fn scroll_by(&mut self, delta: isize, num_rows: usize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(num_rows.saturating_sub(1));
+    }
+}
+
+/// Chart-area view of one table series
+pub struct TableView<'a> {
+    table: &'a Table,
+    /// Index of the logged version shown
+    cursor: usize,
+    state: &'a TableViewState,
+}
+
+impl<'a> TableView<'a> {
+    pub fn new(table: &'a Table, cursor: usize, state: &'a TableViewState) -> Self {
+        let cursor = cursor.min(table.points.len().saturating_sub(1));
+        TableView {
+            table,
+            cursor,
+            state,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let Some(point) = self.table.points.get(self.cursor) else {
+            let block = Block::default()
+                .title(format!(" {} ", self.table.name))
+                .borders(Borders::ALL);
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            frame.render_widget(
+                Paragraph::new("No data").alignment(Alignment::Center),
+                inner,
+            );
+            return;
+        };
+
+        let rows = sorted_rows(&point.rows, self.state);
+        let visible = area.height.saturating_sub(3) as usize; // Borders and header
+        let first = self.state.scroll.min(rows.len().saturating_sub(1));
+        let last = (first + visible).min(rows.len());
+
+        let title = format!(
+            " {} · step {} ({}/{}) ",
+            self.table.name,
+            point.step,
+            self.cursor + 1,
+            self.table.points.len()
+        );
+        let footer = if rows.is_empty() {
+            " no rows ".to_string()
+        } else {
+            format!(" rows {}-{} of {} ", first + 1, last, rows.len())
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(footer)
+            .borders(Borders::ALL);
+
+        let header = Row::new(point.columns.iter().enumerate().map(|(idx, name)| {
+            let arrow = match (self.state.sort_column, self.state.descending) {
+                (Some(col), false) if col == idx => " ▲",
+                (Some(col), true) if col == idx => " ▼",
+                _ => "",
+            };
+            Cell::from(format!("{name}{arrow}"))
+        }))
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        // Size columns by their widest visible content
+        let widths: Vec<Constraint> = point
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                let content = rows[first..last]
+                    .iter()
+                    .filter_map(|row| row.get(idx))
+                    .map(|cell| cell.to_string().chars().count())
+                    .max()
+                    .unwrap_or(0);
+                let width = content.max(name.chars().count() + 2).min(MAX_COLUMN_WIDTH);
+                Constraint::Fill(width as u16)
+            })
+            .collect();
+
+        let body = rows[first..last].iter().map(|row| {
+            Row::new(
+                row.iter()
+                    .map(|cell| Cell::from(cell.to_string().replace('\n', " "))),
+            )
+        });
+        let table = TableWidget::new(body, widths)
+            .header(header)
+            .block(block)
+            .column_spacing(2);
+        frame.render_widget(table, area);
+    }
+}
+
+/// Rows in display order
+fn sorted_rows<'a>(
+    rows: &'a [Vec<ConfigValue>],
+    state: &TableViewState,
+) -> Vec<&'a Vec<ConfigValue>> {
+    let mut sorted: Vec<&Vec<ConfigValue>> = rows.iter().collect();
+    if let Some(col) = state.sort_column {
+        // Stable sort keeps the logged order among equal cells
+        sorted.sort_by(|a, b| compare_cells(a.get(col), b.get(col), state.descending));
+    }
+    sorted
+}
+
+/// Order cells numerically when both are numbers, otherwise by their text;
+/// empty cells sort last in either direction
+fn compare_cells(a: Option<&ConfigValue>, b: Option<&ConfigValue>, descending: bool) -> Ordering {
+    let number = |cell: &ConfigValue| match cell {
+        ConfigValue::Int(v) => Some(*v as f64),
+        ConfigValue::Float(v) => Some(*v),
+        _ => None,
+    };
+    match (a, b) {
+        (None | Some(ConfigValue::Null), None | Some(ConfigValue::Null)) => Ordering::Equal,
+        (None | Some(ConfigValue::Null), _) => Ordering::Greater,
+        (_, None | Some(ConfigValue::Null)) => Ordering::Less,
+        (Some(a), Some(b)) => {
+            let order = match (number(a), number(b)) {
+                (Some(x), Some(y)) => x.total_cmp(&y),
+                _ => a.to_string().cmp(&b.to_string()),
+            };
+            if descending {
+                order.reverse()
+            } else {
+                order
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_rows() {
+        let rows = vec![
+            vec![ConfigValue::String("b".into()), ConfigValue::Int(10)],
+            vec![ConfigValue::String("a".into()), ConfigValue::Null],
+            vec![ConfigValue::String("c".into()), ConfigValue::Float(2.5)],
+        ];
+        let first_cells = |state: &TableViewState| -> Vec<String> {
+            sorted_rows(&rows, state)
+                .iter()
+                .map(|row| row[0].to_string())
+                .collect()
+        };
+
+        let mut state = TableViewState::new();
+        assert_eq!(first_cells(&state), vec!["b", "a", "c"]);
+
+        state.cycle_sort(2);
+        assert_eq!(first_cells(&state), vec!["a", "b", "c"]);
+
+        // Numbers compare numerically, with empty cells last
+        state.cycle_sort(2);
+        assert_eq!(first_cells(&state), vec!["c", "b", "a"]);
+        state.toggle_order();
+        assert_eq!(first_cells(&state), vec!["b", "c", "a"]);
+
+        state.cycle_sort(2);
+        assert_eq!(state.sort_column, None);
+    }
+
+    #[test]
+    fn test_scroll_keeps_a_row_visible() {
+        let mut state = TableViewState::new();
+        state.scroll_by(-3, 5);
+        assert_eq!(state.scroll, 0);
+        state.scroll_by(10, 5);
+        assert_eq!(state.scroll, 4);
+    }
+}
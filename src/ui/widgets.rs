@@ -5,6 +5,7 @@ use std::collections::HashSet;
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
+    text::{Line, Span},
     widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
@@ -75,7 +76,17 @@ impl<'a> ProjectList<'a> {
         let items: Vec<ListItem> = self
             .projects
             .iter()
-            .map(|p| ListItem::new(format!("{} ({})", p.name, p.run_count)))
+            .map(|p| {
+                let mut spans = vec![Span::raw(format!("{} ({})", p.name, p.run_count))];
+                // With several data roots, show where each project lives
+                if let Some(root) = &p.root {
+                    spans.push(Span::styled(
+                        format!("  {root}"),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
             .collect();
 
         let block = Block::default()
@@ -100,19 +111,40 @@ impl<'a> ProjectList<'a> {
     }
 }
 
+/// Index of the item drawn at terminal `row` of a bordered list in `area`, of
+/// `len` items with `selected` highlighted. The lists scroll just far enough
+/// to keep the selection visible.
+pub fn list_index_at(area: Rect, row: u16, selected: usize, len: usize) -> Option<usize> {
+    if row <= area.y || row + 1 >= area.bottom() {
+        return None;
+    }
+    let visible = area.height.saturating_sub(2) as usize;
+    let offset = (selected + 1).saturating_sub(visible);
+    let index = offset + (row - area.y - 1) as usize;
+    (index < len).then_some(index)
+}
+
 /// Run list panel widget
 pub struct RunList<'a> {
     runs: &'a [Run],
     selected: usize,
     marked_ids: &'a HashSet<String>,
+    /// Runs whose latest value of some metric is NaN or infinite
+    diverged_ids: &'a HashSet<String>,
 }
 
 impl<'a> RunList<'a> {
-    pub fn new(runs: &'a [Run], selected: usize, marked_ids: &'a HashSet<String>) -> Self {
+    pub fn new(
+        runs: &'a [Run],
+        selected: usize,
+        marked_ids: &'a HashSet<String>,
+        diverged_ids: &'a HashSet<String>,
+    ) -> Self {
         RunList {
             runs,
             selected,
             marked_ids,
+            diverged_ids,
         }
     }
 
@@ -126,7 +158,11 @@ impl<'a> RunList<'a> {
                 } else {
                     "  "
                 };
-                ListItem::new(format!("{}{}", prefix, r.display_name))
+                let mut spans = vec![Span::raw(format!("{}{}", prefix, r.display_name))];
+                if self.diverged_ids.contains(&r.id) {
+                    spans.push(Span::styled(" ⚠", Style::default().fg(Color::Red)));
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
@@ -152,7 +188,7 @@ impl<'a> RunList<'a> {
     }
 }
 
-/// Config panel state for scrolling and search
+/// Scroll and search state of a text panel (config, log)
 #[derive(Debug, Default)]
 pub struct ConfigPanelState {
     pub scroll_v: u16,
@@ -178,6 +214,67 @@ impl ConfigPanelState {
         self.match_indices.clear();
         self.current_match = 0;
     }
+
+    /// Start typing a new search query
+    pub fn start_search(&mut self) {
+        self.search_active = true;
+        self.clear_search();
+    }
+
+    /// Clear the search query and its matches
+    pub fn clear_search(&mut self) {
+        self.search.clear();
+        self.match_indices.clear();
+        self.current_match = 0;
+    }
+
+    /// Update search match indices of `lines` based on current search query
+    pub fn update_matches(&mut self, lines: &[String]) {
+        self.match_indices.clear();
+        if self.search.is_empty() {
+            return;
+        }
+
+        let query = self.search.to_lowercase();
+        for (idx, line) in lines.iter().enumerate() {
+            if line.to_lowercase().contains(&query) {
+                self.match_indices.push(idx);
+            }
+        }
+
+        // Reset current match if out of bounds
+        if self.current_match >= self.match_indices.len() {
+            self.current_match = 0;
+        }
+    }
+
+    /// Jump to the next search match
+    pub fn next_match(&mut self) {
+        if self.match_indices.is_empty() {
+            return;
+        }
+        self.current_match = (self.current_match + 1) % self.match_indices.len();
+        self.scroll_to_current_match();
+    }
+
+    /// Jump to the previous search match
+    pub fn prev_match(&mut self) {
+        if self.match_indices.is_empty() {
+            return;
+        }
+        self.current_match = self
+            .current_match
+            .checked_sub(1)
+            .unwrap_or(self.match_indices.len() - 1);
+        self.scroll_to_current_match();
+    }
+
+    /// Scroll to make the current match visible
+    pub fn scroll_to_current_match(&mut self) {
+        if let Some(&line_idx) = self.match_indices.get(self.current_match) {
+            self.scroll_v = line_idx as u16;
+        }
+    }
 }
 
 /// Config panel widget
@@ -192,103 +289,112 @@ impl<'a> ConfigPanel<'a> {
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect, focused: bool) {
-        use ratatui::text::{Line, Span};
-
         let lines: Vec<String> = self
             .config
             .iter()
             .map(|c| format!("{}: {}", c.key, c.value))
             .collect();
+        render_search_panel(frame, area, focused, "Config", &lines, self.state);
+    }
+}
 
-        // Build styled lines with search highlighting
-        let styled_lines: Vec<Line> = lines
-            .iter()
-            .enumerate()
-            .map(|(idx, line)| {
-                let is_match_line = self.state.match_indices.contains(&idx);
-                let is_current_match = self
-                    .state
-                    .match_indices
-                    .get(self.state.current_match)
-                    .is_some_and(|&m| m == idx);
-
-                if !self.state.search.is_empty() && is_match_line {
-                    // Highlight matching text (Unicode-safe)
-                    let matches = case_insensitive_byte_ranges(line, &self.state.search);
-                    let mut spans = Vec::new();
-                    let mut last_end = 0;
-
-                    for (start, end) in matches {
-                        if start > last_end {
-                            spans.push(Span::raw(&line[last_end..start]));
-                        }
-                        let style = if is_current_match {
-                            Style::default().fg(Color::Black).bg(Color::Yellow)
-                        } else {
-                            Style::default().fg(Color::Black).bg(Color::DarkGray)
-                        };
-                        spans.push(Span::styled(&line[start..end], style));
-                        last_end = end;
-                    }
-                    if last_end < line.len() {
-                        spans.push(Span::raw(&line[last_end..]));
+/// Render scrollable lines with search highlighting, titled with the search and match info
+/// (shared by the config and log panels)
+pub(super) fn render_search_panel(
+    frame: &mut Frame,
+    area: Rect,
+    focused: bool,
+    name: &str,
+    lines: &[String],
+    state: &ConfigPanelState,
+) {
+    // Build styled lines with search highlighting
+    let styled_lines: Vec<Line> = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let is_match_line = state.match_indices.contains(&idx);
+            let is_current_match = state
+                .match_indices
+                .get(state.current_match)
+                .is_some_and(|&m| m == idx);
+
+            if !state.search.is_empty() && is_match_line {
+                // Highlight matching text (Unicode-safe)
+                let matches = case_insensitive_byte_ranges(line, &state.search);
+                let mut spans = Vec::new();
+                let mut last_end = 0;
+
+                for (start, end) in matches {
+                    if start > last_end {
+                        spans.push(Span::raw(&line[last_end..start]));
                     }
-                    Line::from(spans)
-                } else {
-                    Line::from(line.as_str())
+                    let style = if is_current_match {
+                        Style::default().fg(Color::Black).bg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::Black).bg(Color::DarkGray)
+                    };
+                    spans.push(Span::styled(&line[start..end], style));
+                    last_end = end;
                 }
-            })
-            .collect();
-
-        // Build title with match info
-        let title = if self.state.search_active {
-            if self.state.match_indices.is_empty() && !self.state.search.is_empty() {
-                format!(" Config [/{}] (no matches) ", self.state.search)
-            } else if !self.state.match_indices.is_empty() {
-                format!(
-                    " Config [/{}] ({}/{}) ",
-                    self.state.search,
-                    self.state.current_match + 1,
-                    self.state.match_indices.len()
-                )
-            } else {
-                format!(" Config [/{}] ", self.state.search)
-            }
-        } else if !self.state.search.is_empty() {
-            if self.state.match_indices.is_empty() {
-                format!(" Config [{}] (no matches) ", self.state.search)
+                if last_end < line.len() {
+                    spans.push(Span::raw(&line[last_end..]));
+                }
+                Line::from(spans)
             } else {
-                format!(
-                    " Config [{}] ({}/{}) ",
-                    self.state.search,
-                    self.state.current_match + 1,
-                    self.state.match_indices.len()
-                )
+                Line::from(line.as_str())
             }
+        })
+        .collect();
+
+    // Build title with match info
+    let title = if state.search_active {
+        if state.match_indices.is_empty() && !state.search.is_empty() {
+            format!(" {name} [/{}] (no matches) ", state.search)
+        } else if !state.match_indices.is_empty() {
+            format!(
+                " {name} [/{}] ({}/{}) ",
+                state.search,
+                state.current_match + 1,
+                state.match_indices.len()
+            )
         } else {
-            " Config ".to_string()
-        };
-
-        let block = Block::default()
-            .title(title)
-            .borders(Borders::ALL)
-            .border_type(if focused {
-                BorderType::Double
-            } else {
-                BorderType::Plain
-            })
-            .border_style(if focused {
-                Style::default().fg(Color::Cyan)
-            } else {
-                Style::default()
-            });
+            format!(" {name} [/{}] ", state.search)
+        }
+    } else if !state.search.is_empty() {
+        if state.match_indices.is_empty() {
+            format!(" {name} [{}] (no matches) ", state.search)
+        } else {
+            format!(
+                " {name} [{}] ({}/{}) ",
+                state.search,
+                state.current_match + 1,
+                state.match_indices.len()
+            )
+        }
+    } else {
+        format!(" {name} ")
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(if focused {
+            BorderType::Double
+        } else {
+            BorderType::Plain
+        })
+        .border_style(if focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        });
 
-        let paragraph = Paragraph::new(styled_lines)
-            .block(block)
-            .scroll((self.state.scroll_v, self.state.scroll_h));
+    let paragraph = Paragraph::new(styled_lines)
+        .block(block)
+        .scroll((state.scroll_v, state.scroll_h));
 
-        frame.render_widget(paragraph, area);
-    }
+    frame.render_widget(paragraph, area);
 }
 
 /// Status bar widget